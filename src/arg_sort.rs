@@ -1,6 +1,7 @@
 
 pub trait VecArgSort {
     fn arg_sort(&self) -> Vec<usize>;
+    fn arg_rank(&self) -> Vec<usize>;
 }
 
 impl<T> VecArgSort for [T] where T: Ord {
@@ -9,6 +10,14 @@ impl<T> VecArgSort for [T] where T: Ord {
         indices.sort_by_key(|&i| &self[i]);
         indices
     }
+
+    fn arg_rank(&self) -> Vec<usize> {
+        let mut ranks = vec![0; self.len()];
+        for (rank, i) in self.arg_sort().into_iter().enumerate() {
+            ranks[i] = rank;
+        }
+        ranks
+    }
 }
 
 pub trait VecApplyOrder {
@@ -0,0 +1,95 @@
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+pub trait AsyncBinaryWriteExt {
+    fn write_string_len16(&mut self, value: &str) -> impl Future<Output = io::Result<()>> + Send;
+    fn write_string_len32(&mut self, value: &str) -> impl Future<Output = io::Result<()>> + Send;
+}
+
+impl<W: AsyncWrite + Unpin + Send> AsyncBinaryWriteExt for W {
+    async fn write_string_len16(&mut self, value: &str) -> io::Result<()> {
+        let bytes = value.as_bytes();
+        if bytes.len() > u16::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "String too long"));
+        }
+        self.write_u16(bytes.len() as u16).await?;
+        self.write_all(bytes).await
+    }
+
+    async fn write_string_len32(&mut self, value: &str) -> io::Result<()> {
+        let bytes = value.as_bytes();
+        if bytes.len() > u32::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "String too long"));
+        }
+        self.write_u32(bytes.len() as u32).await?;
+        self.write_all(bytes).await
+    }
+}
+
+pub trait AsyncBinaryReadExt {
+    fn read_string_len16(&mut self) -> impl Future<Output = io::Result<String>> + Send;
+    fn read_string_len32(&mut self) -> impl Future<Output = io::Result<String>> + Send;
+}
+
+impl<R: AsyncRead + Unpin + Send> AsyncBinaryReadExt for R {
+    async fn read_string_len16(&mut self) -> io::Result<String> {
+        let len = self.read_u16().await?;
+        let mut buffer = vec![0u8; len as usize];
+        self.read_exact(&mut buffer).await?;
+        String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    async fn read_string_len32(&mut self) -> io::Result<String> {
+        let len = self.read_u32().await?;
+        let mut buffer = vec![0u8; len as usize];
+        self.read_exact(&mut buffer).await?;
+        String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod async_binary_io_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_matches_sync_encoding() {
+        let mut sync = Vec::new();
+        crate::WriteExt::write_u8(&mut sync, 0xAB).unwrap();
+        crate::WriteExt::write_u16(&mut sync, 0x1234).unwrap();
+        crate::WriteExt::write_u32(&mut sync, 0xDEADBEEF).unwrap();
+        crate::WriteExt::write_u64(&mut sync, u64::MAX - 7).unwrap();
+        crate::WriteExt::write_i64(&mut sync, -42).unwrap();
+        crate::WriteExt::write_f64(&mut sync, std::f64::consts::PI).unwrap();
+        crate::WriteExt::write_string_len16(&mut sync, "hello").unwrap();
+        crate::WriteExt::write_string_len32(&mut sync, "wörld").unwrap();
+
+        let mut asynchronous = Vec::new();
+        asynchronous.write_u8(0xAB).await.unwrap();
+        asynchronous.write_u16(0x1234).await.unwrap();
+        asynchronous.write_u32(0xDEADBEEF).await.unwrap();
+        asynchronous.write_u64(u64::MAX - 7).await.unwrap();
+        asynchronous.write_i64(-42).await.unwrap();
+        asynchronous.write_f64(std::f64::consts::PI).await.unwrap();
+        asynchronous.write_string_len16("hello").await.unwrap();
+        asynchronous.write_string_len32("wörld").await.unwrap();
+        assert_eq!(sync, asynchronous);
+
+        let mut reader = asynchronous.as_slice();
+        assert_eq!(reader.read_u8().await.unwrap(), 0xAB);
+        assert_eq!(reader.read_u16().await.unwrap(), 0x1234);
+        assert_eq!(reader.read_u32().await.unwrap(), 0xDEADBEEF);
+        assert_eq!(reader.read_u64().await.unwrap(), u64::MAX - 7);
+        assert_eq!(reader.read_i64().await.unwrap(), -42);
+        assert_eq!(reader.read_f64().await.unwrap(), std::f64::consts::PI);
+        assert_eq!(reader.read_string_len16().await.unwrap(), "hello");
+        assert_eq!(reader.read_string_len32().await.unwrap(), "wörld");
+        assert!(reader.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_truncated_input() {
+        let mut reader: &[u8] = &[0x00, 0x05, b'a'];
+        let err = reader.read_string_len16().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::io;
+use crate::{ReadExt, WriteExt};
+
+pub trait BinaryCodec: Sized {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()>;
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_codec_primitive {
+    ($($t:ty => $write:ident, $read:ident);* $(;)?) => {
+        $(
+            impl BinaryCodec for $t {
+                fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.$write(*self)
+                }
+
+                fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+                    reader.$read()
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_primitive! {
+    u8 => write_u8, read_u8;
+    u16 => write_u16, read_u16;
+    u32 => write_u32, read_u32;
+    u64 => write_u64, read_u64;
+    i64 => write_i64, read_i64;
+    f64 => write_f64, read_f64;
+}
+
+macro_rules! impl_codec_cast {
+    ($($t:ty as $u:ty),* $(,)?) => {
+        $(
+            impl BinaryCodec for $t {
+                fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+                    (*self as $u).encode(writer)
+                }
+
+                fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+                    Ok(<$u>::decode(reader)? as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_cast!(i8 as u8, i16 as u16, i32 as u32);
+
+impl BinaryCodec for f32 {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_u32(self.to_bits())
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(f32::from_bits(reader.read_u32()?))
+    }
+}
+
+impl BinaryCodec for bool {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_u8(*self as u8)
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        match reader.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid bool")),
+        }
+    }
+}
+
+impl BinaryCodec for String {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_string_len32(self)
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        reader.read_string_len32()
+    }
+}
+
+fn write_len<W: io::Write>(writer: &mut W, len: usize) -> io::Result<()> {
+    if len > u32::MAX as usize {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Collection too long"));
+    }
+    writer.write_u32(len as u32)
+}
+
+// Cap preallocation so a corrupt length cannot trigger a huge allocation up front.
+fn read_len<R: io::Read>(reader: &mut R) -> io::Result<(usize, usize)> {
+    let len = reader.read_u32()? as usize;
+    Ok((len, len.min(4096)))
+}
+
+impl<T: BinaryCodec> BinaryCodec for Vec<T> {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_len(writer, self.len())?;
+        self.iter().try_for_each(|x| x.encode(writer))
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let (len, capacity) = read_len(reader)?;
+        let mut values = Vec::with_capacity(capacity);
+        for _ in 0..len {
+            values.push(T::decode(reader)?);
+        }
+        Ok(values)
+    }
+}
+
+impl<T: BinaryCodec> BinaryCodec for Option<T> {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            None => writer.write_u8(0),
+            Some(value) => {
+                writer.write_u8(1)?;
+                value.encode(writer)
+            }
+        }
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        match reader.read_u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(reader)?)),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid option tag")),
+        }
+    }
+}
+
+impl<T: BinaryCodec, const N: usize> BinaryCodec for [T; N] {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.iter().try_for_each(|x| x.encode(writer))
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let mut values = Vec::with_capacity(N);
+        for _ in 0..N {
+            values.push(T::decode(reader)?);
+        }
+        Ok(values.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+}
+
+impl<K: BinaryCodec + Ord, V: BinaryCodec> BinaryCodec for BTreeMap<K, V> {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_len(writer, self.len())?;
+        for (k, v) in self {
+            k.encode(writer)?;
+            v.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let (len, _) = read_len(reader)?;
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            map.insert(K::decode(reader)?, V::decode(reader)?);
+        }
+        Ok(map)
+    }
+}
+
+impl<K: BinaryCodec + Eq + Hash, V: BinaryCodec> BinaryCodec for HashMap<K, V> {
+    fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_len(writer, self.len())?;
+        for (k, v) in self {
+            k.encode(writer)?;
+            v.encode(writer)?;
+        }
+        Ok(())
+    }
+
+    fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let (len, capacity) = read_len(reader)?;
+        let mut map = HashMap::with_capacity(capacity);
+        for _ in 0..len {
+            map.insert(K::decode(reader)?, V::decode(reader)?);
+        }
+        Ok(map)
+    }
+}
+
+macro_rules! impl_codec_tuple {
+    ($($name:ident)+) => {
+        impl<$($name: BinaryCodec),+> BinaryCodec for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encode<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+                let ($($name,)+) = self;
+                $($name.encode(writer)?;)+
+                Ok(())
+            }
+
+            fn decode<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+                Ok(($($name::decode(reader)?,)+))
+            }
+        }
+    };
+}
+
+impl_codec_tuple!(A);
+impl_codec_tuple!(A B);
+impl_codec_tuple!(A B C);
+impl_codec_tuple!(A B C D);
+impl_codec_tuple!(A B C D E);
+impl_codec_tuple!(A B C D E F);
+
+#[cfg(test)]
+mod binary_codec_tests {
+    use super::*;
+
+    fn round_trip<T: BinaryCodec + PartialEq + std::fmt::Debug>(value: T) {
+        let mut bytes = Vec::new();
+        value.encode(&mut bytes).unwrap();
+        let mut reader = bytes.as_slice();
+        assert_eq!(T::decode(&mut reader).unwrap(), value);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(-5i8);
+        round_trip(-1234i32);
+        round_trip(1.5f32);
+        round_trip(true);
+        round_trip(String::from("hello"));
+        round_trip(vec![Some(1u16), None, Some(3)]);
+        round_trip([(1u8, String::from("a")), (2, String::from("b"))]);
+        round_trip(BTreeMap::from([(1u64, vec![1.0f64]), (2, vec![])]));
+        round_trip(HashMap::from([(String::from("k"), (None::<i64>, false))]));
+        round_trip(vec![BTreeMap::from([(0u32, [Some(vec![String::new()]), None])])]);
+    }
+
+    #[test]
+    fn test_matches_write_ext() {
+        let mut bytes = Vec::new();
+        (7u32, String::from("x")).encode(&mut bytes).unwrap();
+        let mut expected = Vec::new();
+        expected.write_u32(7).unwrap();
+        expected.write_string_len32("x").unwrap();
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn test_invalid_tag() {
+        let mut reader: &[u8] = &[2];
+        assert_eq!(Option::<u8>::decode(&mut reader).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
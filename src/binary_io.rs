@@ -1,5 +1,7 @@
 use std::io;
 
+pub const DEFAULT_MAX_CSTRING_LEN: usize = 1024 * 1024;
+
 pub fn read_be_u16(slice: &[u8]) -> u16 {
     let mut bytes = [0u8; 2];
     bytes.copy_from_slice(slice);
@@ -39,6 +41,10 @@ pub trait WriteExt {
     fn write_f64(&mut self, value: f64) -> io::Result<()>;
     fn write_string_len16(&mut self, value: &str) -> io::Result<()>;
     fn write_string_len32(&mut self, value: &str) -> io::Result<()>;
+    fn write_cstring(&mut self, value: &str) -> io::Result<()>;
+    fn write_fixed_string(&mut self, value: &str, width: usize) -> io::Result<()>;
+    fn write_utf16le_len16(&mut self, value: &str) -> io::Result<()>;
+    fn write_utf16be_len16(&mut self, value: &str) -> io::Result<()>;
 }
 
 impl<W: io::Write> WriteExt for W {
@@ -83,6 +89,47 @@ impl<W: io::Write> WriteExt for W {
         self.write_u32(bytes.len() as u32)?;
         self.write_all(bytes)
     }
+
+    fn write_cstring(&mut self, value: &str) -> io::Result<()> {
+        let bytes = value.as_bytes();
+        if bytes.contains(&0) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "String contains NUL"));
+        }
+        self.write_all(bytes)?;
+        self.write_u8(0)
+    }
+
+    fn write_fixed_string(&mut self, value: &str, width: usize) -> io::Result<()> {
+        let bytes = value.as_bytes();
+        if bytes.contains(&0) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "String contains NUL"));
+        }
+        if bytes.len() > width {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "String too long"));
+        }
+        self.write_all(bytes)?;
+        self.write_all(&vec![0u8; width - bytes.len()])
+    }
+
+    fn write_utf16le_len16(&mut self, value: &str) -> io::Result<()> {
+        let units = encode_utf16_len16(value)?;
+        self.write_all(&(units.len() as u16).to_le_bytes())?;
+        units.iter().try_for_each(|unit| self.write_all(&unit.to_le_bytes()))
+    }
+
+    fn write_utf16be_len16(&mut self, value: &str) -> io::Result<()> {
+        let units = encode_utf16_len16(value)?;
+        self.write_u16(units.len() as u16)?;
+        units.iter().try_for_each(|&unit| self.write_u16(unit))
+    }
+}
+
+fn encode_utf16_len16(value: &str) -> io::Result<Vec<u16>> {
+    let units = value.encode_utf16().collect::<Vec<_>>();
+    if units.len() > u16::MAX as usize {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "String too long"));
+    }
+    Ok(units)
 }
 
 pub trait ReadExt {
@@ -94,6 +141,15 @@ pub trait ReadExt {
     fn read_f64(&mut self) -> io::Result<f64>;
     fn read_string_len16(&mut self) -> io::Result<String>;
     fn read_string_len32(&mut self) -> io::Result<String>;
+    fn read_string_len16_lossy(&mut self) -> io::Result<String>;
+    fn read_string_len32_lossy(&mut self) -> io::Result<String>;
+    fn read_cstring(&mut self) -> io::Result<String>;
+    fn read_cstring_max(&mut self, max_len: usize) -> io::Result<String>;
+    fn read_cstring_lossy(&mut self) -> io::Result<String>;
+    fn read_fixed_string(&mut self, width: usize) -> io::Result<String>;
+    fn read_fixed_string_lossy(&mut self, width: usize) -> io::Result<String>;
+    fn read_utf16le_len16(&mut self) -> io::Result<String>;
+    fn read_utf16be_len16(&mut self) -> io::Result<String>;
 }
 
 impl<R: io::Read> ReadExt for R {
@@ -146,4 +202,152 @@ impl<R: io::Read> ReadExt for R {
         self.read_exact(&mut buffer)?;
         String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
-}
\ No newline at end of file
+
+    fn read_string_len16_lossy(&mut self) -> io::Result<String> {
+        let len = self.read_u16()? as usize;
+        let mut buffer = vec![0u8; len];
+        self.read_exact(&mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    fn read_string_len32_lossy(&mut self) -> io::Result<String> {
+        let len = self.read_u32()? as usize;
+        let mut buffer = vec![0u8; len];
+        self.read_exact(&mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    fn read_cstring(&mut self) -> io::Result<String> {
+        self.read_cstring_max(DEFAULT_MAX_CSTRING_LEN)
+    }
+
+    fn read_cstring_max(&mut self, max_len: usize) -> io::Result<String> {
+        let buffer = read_until_nul(self, max_len)?;
+        String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn read_cstring_lossy(&mut self) -> io::Result<String> {
+        let buffer = read_until_nul(self, DEFAULT_MAX_CSTRING_LEN)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    fn read_fixed_string(&mut self, width: usize) -> io::Result<String> {
+        let mut buffer = vec![0u8; width];
+        self.read_exact(&mut buffer)?;
+        buffer.truncate(buffer.iter().position(|&b| b == 0).unwrap_or(width));
+        String::from_utf8(buffer).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn read_fixed_string_lossy(&mut self, width: usize) -> io::Result<String> {
+        let mut buffer = vec![0u8; width];
+        self.read_exact(&mut buffer)?;
+        buffer.truncate(buffer.iter().position(|&b| b == 0).unwrap_or(width));
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+
+    fn read_utf16le_len16(&mut self) -> io::Result<String> {
+        let mut len = [0u8; 2];
+        self.read_exact(&mut len)?;
+        let mut buffer = vec![0u8; u16::from_le_bytes(len) as usize * 2];
+        self.read_exact(&mut buffer)?;
+        let units = buffer.chunks_exact(2).map(|x| u16::from_le_bytes([x[0], x[1]])).collect::<Vec<_>>();
+        String::from_utf16(&units).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn read_utf16be_len16(&mut self) -> io::Result<String> {
+        let len = self.read_u16()? as usize;
+        let mut buffer = vec![0u8; len * 2];
+        self.read_exact(&mut buffer)?;
+        let units = buffer.chunks_exact(2).map(read_be_u16).collect::<Vec<_>>();
+        String::from_utf16(&units).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn read_until_nul<R: io::Read + ?Sized>(reader: &mut R, max_len: usize) -> io::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let mut byte = [0u8; 1];
+    loop {
+        reader.read_exact(&mut byte)?;
+        if byte[0] == 0 {
+            return Ok(buffer)
+        }
+        if buffer.len() == max_len {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "String too long"));
+        }
+        buffer.push(byte[0]);
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
+
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+
+#[cfg(test)]
+mod binary_io_tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_string_encodings() {
+        let mut bytes = Vec::new();
+        bytes.write_cstring("héllo").unwrap();
+        bytes.write_fixed_string("abc", 6).unwrap();
+        bytes.write_fixed_string("abcdef", 6).unwrap();
+        bytes.write_utf16le_len16("a𝄞").unwrap();
+        bytes.write_utf16be_len16("a𝄞").unwrap();
+        assert_eq!(&bytes[..7], b"h\xc3\xa9llo\0");
+        assert_eq!(&bytes[19..27], [3, 0, b'a', 0, 0x34, 0xD8, 0x1E, 0xDD]);
+        assert_eq!(&bytes[27..], [0, 3, 0, b'a', 0xD8, 0x34, 0xDD, 0x1E]);
+
+        let mut reader = bytes.as_slice();
+        assert_eq!(reader.read_cstring().unwrap(), "héllo");
+        assert_eq!(reader.read_fixed_string(6).unwrap(), "abc");
+        assert_eq!(reader.read_fixed_string(6).unwrap(), "abcdef");
+        assert_eq!(reader.read_utf16le_len16().unwrap(), "a𝄞");
+        assert_eq!(reader.read_utf16be_len16().unwrap(), "a𝄞");
+        assert!(reader.is_empty());
+
+        assert!(Vec::new().write_cstring("a\0b").is_err());
+        assert!(Vec::new().write_fixed_string("abcdefg", 6).is_err());
+        assert!(Vec::new().write_fixed_string("ab\0c", 6).is_err());
+        assert_eq!((&b"abc\0"[..]).read_cstring_max(3).unwrap(), "abc");
+        assert_eq!((&b"abcd\0"[..]).read_cstring_max(3).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let unterminated = std::io::repeat(b'a').take(DEFAULT_MAX_CSTRING_LEN as u64 + 1);
+        assert_eq!(unterminated.chain(&[0][..]).read_cstring().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_lossy() {
+        let bytes = [0u8, 3, b'a', 0xFF, b'b'];
+        assert_eq!((&bytes[..]).read_string_len16().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!((&bytes[..]).read_string_len16_lossy().unwrap(), "a\u{FFFD}b");
+        assert_eq!((&bytes[2..]).read_cstring_lossy().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!((&[b'a', 0xFF, 0][..]).read_cstring_lossy().unwrap(), "a\u{FFFD}");
+        assert_eq!((&bytes[2..]).read_fixed_string_lossy(3).unwrap(), "a\u{FFFD}b");
+    }
+}
@@ -0,0 +1,200 @@
+use std::io;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
+
+pub struct BitWriter<W> {
+    inner: W,
+    order: BitOrder,
+    current: u8,
+    filled: u32,
+}
+
+impl<W: io::Write> BitWriter<W> {
+    pub fn new(inner: W, order: BitOrder) -> Self {
+        Self { inner, order, current: 0, filled: 0 }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) -> io::Result<()> {
+        let shift = match self.order {
+            BitOrder::MsbFirst => 7 - self.filled,
+            BitOrder::LsbFirst => self.filled,
+        };
+        self.current |= (bit as u8) << shift;
+        self.filled += 1;
+        if self.filled == 8 {
+            self.inner.write_all(&[self.current])?;
+            self.current = 0;
+            self.filled = 0;
+        }
+        Ok(())
+    }
+
+    pub fn write_bits(&mut self, value: u64, count: u32) -> io::Result<()> {
+        if count > 64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Bit count exceeds 64"));
+        }
+        if count < 64 && value >> count != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Value does not fit in bit count"));
+        }
+        match self.order {
+            BitOrder::MsbFirst => (0..count).rev().try_for_each(|i| self.write_bit(value >> i & 1 != 0)),
+            BitOrder::LsbFirst => (0..count).try_for_each(|i| self.write_bit(value >> i & 1 != 0)),
+        }
+    }
+
+    pub fn is_aligned(&self) -> bool {
+        self.filled == 0
+    }
+
+    pub fn align(&mut self) -> io::Result<()> {
+        while !self.is_aligned() {
+            self.write_bit(false)?;
+        }
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.align()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: io::Write> io::Write for BitWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.is_aligned() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "BitWriter is not byte aligned"));
+        }
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub struct BitReader<R> {
+    inner: R,
+    order: BitOrder,
+    current: u8,
+    remaining: u32,
+}
+
+impl<R: io::Read> BitReader<R> {
+    pub fn new(inner: R, order: BitOrder) -> Self {
+        Self { inner, order, current: 0, remaining: 0 }
+    }
+
+    pub fn read_bit(&mut self) -> io::Result<bool> {
+        if self.remaining == 0 {
+            let mut buffer = [0u8; 1];
+            self.inner.read_exact(&mut buffer)?;
+            self.current = buffer[0];
+            self.remaining = 8;
+        }
+        let shift = match self.order {
+            BitOrder::MsbFirst => self.remaining - 1,
+            BitOrder::LsbFirst => 8 - self.remaining,
+        };
+        self.remaining -= 1;
+        Ok(self.current >> shift & 1 != 0)
+    }
+
+    pub fn read_bits(&mut self, count: u32) -> io::Result<u64> {
+        if count > 64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Bit count exceeds 64"));
+        }
+        let mut value = 0u64;
+        for i in 0..count {
+            let bit = self.read_bit()? as u64;
+            match self.order {
+                BitOrder::MsbFirst => value = value << 1 | bit,
+                BitOrder::LsbFirst => value |= bit << i,
+            }
+        }
+        Ok(value)
+    }
+
+    pub fn is_aligned(&self) -> bool {
+        self.remaining == 0
+    }
+
+    pub fn align(&mut self) {
+        self.remaining = 0;
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> io::Read for BitReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.is_aligned() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "BitReader is not byte aligned"));
+        }
+        self.inner.read(buf)
+    }
+}
+
+#[cfg(test)]
+mod bit_io_tests {
+    use super::*;
+    use crate::{ReadExt, WriteExt};
+
+    #[test]
+    fn test_bit_layout() {
+        let mut writer = BitWriter::new(Vec::new(), BitOrder::MsbFirst);
+        writer.write_bits(0b101, 3).unwrap();
+        writer.write_bits(0b1, 1).unwrap();
+        writer.write_bits(0xABC, 12).unwrap();
+        assert_eq!(writer.finish().unwrap(), [0b1011_1010, 0xBC]);
+
+        let mut writer = BitWriter::new(Vec::new(), BitOrder::LsbFirst);
+        writer.write_bits(0b101, 3).unwrap();
+        writer.write_bits(0b1, 1).unwrap();
+        writer.write_bits(0xABC, 12).unwrap();
+        assert_eq!(writer.finish().unwrap(), [0b1100_1101, 0xAB]);
+    }
+
+    #[test]
+    fn test_round_trip_with_byte_interop() {
+        for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            let mut writer = BitWriter::new(Vec::new(), order);
+            writer.write_bits(5, 3).unwrap();
+            writer.write_bits(u64::MAX, 64).unwrap();
+            assert!(writer.write_u16(1).is_err());
+            writer.align().unwrap();
+            writer.write_u16(0xBEEF).unwrap();
+            writer.write_bits(0x3FF, 10).unwrap();
+            let bytes = writer.finish().unwrap();
+
+            let mut reader = BitReader::new(bytes.as_slice(), order);
+            assert_eq!(reader.read_bits(3).unwrap(), 5);
+            assert_eq!(reader.read_bits(64).unwrap(), u64::MAX);
+            assert!(reader.read_u16().is_err());
+            reader.align();
+            assert_eq!(reader.read_u16().unwrap(), 0xBEEF);
+            assert_eq!(reader.read_bits(10).unwrap(), 0x3FF);
+            reader.align();
+            assert!(reader.read_bit().is_err());
+        }
+    }
+
+    #[test]
+    fn test_value_overflow() {
+        let mut writer = BitWriter::new(Vec::new(), BitOrder::MsbFirst);
+        assert_eq!(writer.write_bits(8, 3).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
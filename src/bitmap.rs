@@ -1,3 +1,6 @@
+use std::fmt::{Debug, Formatter};
+
+#[derive(Clone)]
 pub struct Bitmap {
     data: Vec<u128>,
     len: usize,
@@ -6,7 +9,7 @@ pub struct Bitmap {
 impl Bitmap {
     pub fn new(len: usize) -> Self {
         let mut data = vec![0; len.div_ceil(128)];
-        if len % 128 != 0 {
+        if !len.is_multiple_of(128) {
             data[len.div_ceil(128) - 1] = !0 << (len % 128);
         }
         Self { data, len }
@@ -70,17 +73,32 @@ impl Bitmap {
 
     pub fn inverse(&self) -> Self {
         let mut data: Vec<u128> = self.data.iter().map(|&x| !x).collect();
-        if self.len % 128 != 0 {
+        if !self.len.is_multiple_of(128) {
             data[self.len.div_ceil(128) - 1] &= !(!0 << (self.len % 128));
         }
         Self { data, len: self.len }
     }
 
-    pub fn iter(&self) -> BitmapIter {
+    pub fn iter(&self) -> BitmapIter<'_> {
         BitmapIter { bitmap: self, current: 0 }
     }
 }
 
+impl PartialEq for Bitmap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && (0..self.len).all(|i| self.get(i) == other.get(i))
+    }
+}
+
+impl Eq for Bitmap {}
+
+impl Debug for Bitmap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bits = (0..self.len).map(|i| if self.get(i) { '1' } else { '0' }).collect::<String>();
+        f.debug_struct("Bitmap").field("len", &self.len).field("bits", &bits).finish()
+    }
+}
+
 pub struct BitmapIter<'a> {
     bitmap: &'a Bitmap,
     current: usize
@@ -129,4 +147,14 @@ mod bitmap_tests {
         }
         assert!(!bitmap.filled());
     }
+
+    #[test]
+    fn test_equality_ignores_padding() {
+        assert_eq!(Bitmap::new_filled(3), Bitmap::new(3).inverse());
+        assert_eq!(Bitmap::new(130).inverse().inverse(), Bitmap::new(130));
+        assert_ne!(Bitmap::new(3), Bitmap::new(4));
+        let mut bitmap = Bitmap::new(3);
+        bitmap.set(1, true);
+        assert_eq!(format!("{bitmap:?}"), "Bitmap { len: 3, bits: \"010\" }");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::env;
+use std::fmt::{Display, Formatter, Write as _};
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::TargetTriple;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct BuildInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub git_revision: Option<&'static str>,
+    pub git_dirty: bool,
+    pub timestamp: Option<&'static str>,
+    pub profile: Option<&'static str>,
+    pub target: Option<&'static str>,
+    pub rustc_version: Option<&'static str>,
+    pub features: &'static str,
+}
+
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::BuildInfo {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            git_revision: option_env!("FERRITE_BUILD_GIT_REVISION"),
+            git_dirty: option_env!("FERRITE_BUILD_GIT_DIRTY").is_some(),
+            timestamp: option_env!("FERRITE_BUILD_TIMESTAMP"),
+            profile: option_env!("FERRITE_BUILD_PROFILE"),
+            target: option_env!("FERRITE_BUILD_TARGET"),
+            rustc_version: option_env!("FERRITE_BUILD_RUSTC_VERSION"),
+            features: match option_env!("FERRITE_BUILD_FEATURES") {
+                Some(features) => features,
+                None => "",
+            },
+        }
+    };
+}
+
+impl BuildInfo {
+    pub fn features(&self) -> impl Iterator<Item=&'static str> {
+        self.features.split(',').filter(|feature| !feature.is_empty())
+    }
+
+    pub fn short_revision(&self) -> Option<&'static str> {
+        self.git_revision.map(|revision| revision.char_indices().nth(12).map_or(revision, |(i, _)| &revision[..i]))
+    }
+
+    pub fn target_triple(&self) -> Option<TargetTriple> {
+        self.target?.parse().ok()
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        let mut field = |key: &str, value: Option<&str>| {
+            if json.len() > 1 {
+                json.push(',');
+            }
+            write!(json, "\"{key}\":").unwrap();
+            match value {
+                Some(value) => push_json_string(&mut json, value),
+                None => json.push_str("null"),
+            }
+        };
+        field("name", Some(self.name));
+        field("version", Some(self.version));
+        field("git_revision", self.git_revision);
+        field("timestamp", self.timestamp);
+        field("profile", self.profile);
+        field("target", self.target);
+        field("rustc_version", self.rustc_version);
+        write!(json, ",\"git_dirty\":{},\"features\":[", self.git_dirty).unwrap();
+        for (i, feature) in self.features().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_string(&mut json, feature);
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl Display for BuildInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name, self.version)?;
+        if let Some(revision) = self.short_revision() {
+            write!(f, " ({revision}{})", if self.git_dirty { "-dirty" } else { "" })?;
+        }
+        let details = [
+            ("built", self.timestamp),
+            ("profile", self.profile),
+            ("target", self.target),
+            ("rustc", self.rustc_version),
+        ];
+        for (key, value) in details {
+            if let Some(value) = value {
+                write!(f, "\n{key}: {value}")?;
+            }
+        }
+        if !self.features.is_empty() {
+            write!(f, "\nfeatures: {}", self.features().collect::<Vec<_>>().join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn push_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+pub fn emit_build_info() {
+    for (key, value) in collect_build_info() {
+        println!("cargo:rustc-env=FERRITE_BUILD_{key}={value}");
+    }
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
+        for source in ["Cargo.toml", "build.rs", "src"] {
+            let path = Path::new(&dir).join(source);
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+    if let Some(git_dir) = command_output("git", &["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={git_dir}/HEAD");
+        println!("cargo:rerun-if-changed={git_dir}/index");
+        if let Some(head_ref) = command_output("git", &["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={git_dir}/{head_ref}");
+        }
+    }
+}
+
+fn collect_build_info() -> Vec<(&'static str, String)> {
+    let mut info = Vec::new();
+    if let Some(revision) = command_output("git", &["rev-parse", "HEAD"]) {
+        info.push(("GIT_REVISION", revision));
+        if command_output("git", &["status", "--porcelain", "--untracked-files=no"]).is_some() {
+            info.push(("GIT_DIRTY", "true".into()));
+        }
+    }
+    let seconds = env::var("SOURCE_DATE_EPOCH").ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .unwrap_or_else(|| SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()));
+    info.push(("TIMESTAMP", format_timestamp(seconds)));
+    if let Ok(profile) = env::var("PROFILE") {
+        info.push(("PROFILE", profile));
+    }
+    if let Ok(target) = env::var("TARGET") {
+        info.push(("TARGET", target));
+    }
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    if let Some(version) = command_output(&rustc, &["--version"]) {
+        info.push(("RUSTC_VERSION", version));
+    }
+    let features = env::var("CARGO_CFG_FEATURE").unwrap_or_default();
+    let mut features = features.split(',').filter(|feature| !feature.is_empty()).collect::<Vec<_>>();
+    features.sort_unstable();
+    info.push(("FEATURES", features.join(",")));
+    info
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+    if let Ok(dir) = env::var("CARGO_MANIFEST_DIR") {
+        command.current_dir(Path::new(&dir));
+    }
+    let output = command.output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let stdout = stdout.trim();
+    (output.status.success() && !stdout.is_empty()).then(|| stdout.to_string())
+}
+
+fn format_timestamp(seconds: u64) -> String {
+    let (days, time) = (seconds / 86400, seconds % 86400);
+    // Converts days since the epoch to a proleptic Gregorian date, after Howard Hinnant.
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", time / 3600, time / 60 % 60, time % 60)
+}
+
+#[cfg(test)]
+mod build_info_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_timestamp(1_792_418_645), "2026-10-19T14:04:05Z");
+    }
+
+    #[test]
+    fn test_display_and_json() {
+        let info = BuildInfo {
+            name: "tool",
+            version: "1.2.3",
+            git_revision: Some("0123456789abcdef0123456789abcdef01234567"),
+            git_dirty: true,
+            timestamp: Some("2026-10-19T14:04:05Z"),
+            profile: Some("release"),
+            target: Some("x86_64-unknown-linux-musl"),
+            rustc_version: Some("rustc 1.90.0 (\"stable\")"),
+            features: "async,mmap",
+        };
+        assert_eq!(info.to_string(), "\
+tool 1.2.3 (0123456789ab-dirty)
+built: 2026-10-19T14:04:05Z
+profile: release
+target: x86_64-unknown-linux-musl
+rustc: rustc 1.90.0 (\"stable\")
+features: async, mmap");
+        assert_eq!(info.target_triple().unwrap().env, crate::TargetEnv::Musl);
+        assert_eq!(info.to_json(), concat!(
+            r#"{"name":"tool","version":"1.2.3","git_revision":"0123456789abcdef0123456789abcdef01234567","#,
+            r#""timestamp":"2026-10-19T14:04:05Z","profile":"release","target":"x86_64-unknown-linux-musl","#,
+            r#""rustc_version":"rustc 1.90.0 (\"stable\")","git_dirty":true,"features":["async","mmap"]}"#,
+        ));
+    }
+
+    #[test]
+    fn test_macro() {
+        const INFO: BuildInfo = crate::build_info!();
+        assert_eq!(INFO.name, "ferrite");
+        assert_eq!(INFO.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(INFO.git_revision, None);
+        assert_eq!(INFO.features().count(), 0);
+        assert!(INFO.to_json().contains("\"git_revision\":null"));
+        assert!(collect_build_info().iter().any(|(key, _)| *key == "TIMESTAMP"));
+    }
+
+    #[test]
+    fn test_short_revision() {
+        let mut info = crate::build_info!();
+        info.git_revision = Some("ünïcödé-révision-ñame");
+        assert_eq!(info.short_revision(), Some("ünïcödé-révi"));
+        info.git_revision = Some("abc");
+        assert_eq!(info.short_revision(), Some("abc"));
+    }
+}
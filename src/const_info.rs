@@ -1,3 +1,7 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum TargetArch {
@@ -8,7 +12,13 @@ pub enum TargetArch {
     RiscV32,
     RiscV64,
     Mips,
+    Mips64,
+    PowerPC64,
+    S390x,
+    Sparc64,
     Loong64,
+    Wasm32,
+    Other(&'static str),
 }
 
 #[cfg(target_arch = "x86")]
@@ -25,8 +35,25 @@ pub const TARGET_ARCH: TargetArch = TargetArch::RiscV32;
 pub const TARGET_ARCH: TargetArch = TargetArch::RiscV64;
 #[cfg(target_arch = "mips")]
 pub const TARGET_ARCH: TargetArch = TargetArch::Mips;
+#[cfg(target_arch = "mips64")]
+pub const TARGET_ARCH: TargetArch = TargetArch::Mips64;
+#[cfg(target_arch = "powerpc64")]
+pub const TARGET_ARCH: TargetArch = TargetArch::PowerPC64;
+#[cfg(target_arch = "s390x")]
+pub const TARGET_ARCH: TargetArch = TargetArch::S390x;
+#[cfg(target_arch = "sparc64")]
+pub const TARGET_ARCH: TargetArch = TargetArch::Sparc64;
 #[cfg(target_arch = "loongarch64")]
 pub const TARGET_ARCH: TargetArch = TargetArch::Loong64;
+#[cfg(target_arch = "wasm32")]
+pub const TARGET_ARCH: TargetArch = TargetArch::Wasm32;
+#[cfg(not(any(
+    target_arch = "x86", target_arch = "x86_64", target_arch = "arm", target_arch = "aarch64",
+    target_arch = "riscv32", target_arch = "riscv64", target_arch = "mips",
+    target_arch = "mips64", target_arch = "powerpc64", target_arch = "s390x",
+    target_arch = "sparc64", target_arch = "loongarch64", target_arch = "wasm32",
+)))]
+pub const TARGET_ARCH: TargetArch = TargetArch::Other(std::env::consts::ARCH);
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum TargetSystem {
@@ -39,7 +66,12 @@ pub enum TargetSystem {
     DragonFly,
     NetBSD,
     OpenBSD,
+    Illumos,
+    Solaris,
+    Haiku,
+    Wasi,
     BareMetal,
+    Other(&'static str),
 }
 
 #[cfg(target_os = "windows")]
@@ -60,5 +92,525 @@ pub const TARGET_SYSTEM: TargetSystem = TargetSystem::DragonFly;
 pub const TARGET_SYSTEM: TargetSystem = TargetSystem::NetBSD;
 #[cfg(target_os = "openbsd")]
 pub const TARGET_SYSTEM: TargetSystem = TargetSystem::OpenBSD;
+#[cfg(target_os = "illumos")]
+pub const TARGET_SYSTEM: TargetSystem = TargetSystem::Illumos;
+#[cfg(target_os = "solaris")]
+pub const TARGET_SYSTEM: TargetSystem = TargetSystem::Solaris;
+#[cfg(target_os = "haiku")]
+pub const TARGET_SYSTEM: TargetSystem = TargetSystem::Haiku;
+#[cfg(target_os = "wasi")]
+pub const TARGET_SYSTEM: TargetSystem = TargetSystem::Wasi;
 #[cfg(target_os = "none")]
-pub const TARGET_SYSTEM: TargetSystem = TargetSystem::BareMetal;
\ No newline at end of file
+pub const TARGET_SYSTEM: TargetSystem = TargetSystem::BareMetal;
+#[cfg(not(any(
+    target_os = "windows", target_os = "linux", target_os = "macos", target_os = "android",
+    target_os = "ios", target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd",
+    target_os = "openbsd", target_os = "illumos", target_os = "solaris", target_os = "haiku",
+    target_os = "wasi", target_os = "none",
+)))]
+pub const TARGET_SYSTEM: TargetSystem = TargetSystem::Other(std::env::consts::OS);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TargetEnv {
+    Gnu,
+    Musl,
+    Msvc,
+    None,
+    Other(&'static str),
+}
+
+#[cfg(target_env = "gnu")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Gnu;
+#[cfg(target_env = "musl")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Musl;
+#[cfg(target_env = "msvc")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Msvc;
+#[cfg(target_env = "")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::None;
+#[cfg(target_env = "sgx")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("sgx");
+#[cfg(target_env = "uclibc")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("uclibc");
+#[cfg(target_env = "newlib")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("newlib");
+#[cfg(target_env = "ohos")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("ohos");
+#[cfg(target_env = "p1")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("p1");
+#[cfg(target_env = "p2")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("p2");
+#[cfg(target_env = "sim")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("sim");
+#[cfg(target_env = "macabi")]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("macabi");
+#[cfg(not(any(
+    target_env = "gnu", target_env = "musl", target_env = "msvc", target_env = "",
+    target_env = "sgx", target_env = "uclibc", target_env = "newlib", target_env = "ohos",
+    target_env = "p1", target_env = "p2", target_env = "sim", target_env = "macabi",
+)))]
+pub const TARGET_ENV: TargetEnv = TargetEnv::Other("unknown");
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum TargetFamily {
+    Unix,
+    Windows,
+    Wasm,
+    None,
+}
+
+#[cfg(unix)]
+pub const TARGET_FAMILY: TargetFamily = TargetFamily::Unix;
+#[cfg(windows)]
+pub const TARGET_FAMILY: TargetFamily = TargetFamily::Windows;
+#[cfg(all(target_family = "wasm", not(unix)))]
+pub const TARGET_FAMILY: TargetFamily = TargetFamily::Wasm;
+#[cfg(not(any(unix, windows, target_family = "wasm")))]
+pub const TARGET_FAMILY: TargetFamily = TargetFamily::None;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+#[cfg(target_endian = "little")]
+pub const TARGET_ENDIAN: Endianness = Endianness::Little;
+#[cfg(target_endian = "big")]
+pub const TARGET_ENDIAN: Endianness = Endianness::Big;
+
+pub const TARGET_POINTER_WIDTH: u32 = usize::BITS;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseTargetError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl Display for ParseTargetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown target {} `{}`", self.kind, self.value)
+    }
+}
+
+impl Error for ParseTargetError {}
+
+fn parse_error<T>(kind: &'static str, value: &str) -> Result<T, ParseTargetError> {
+    Err(ParseTargetError { kind, value: value.to_string() })
+}
+
+impl TargetArch {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TargetArch::X86 => "x86",
+            TargetArch::X86_64 => "x86_64",
+            TargetArch::Arm => "arm",
+            TargetArch::AArch64 => "aarch64",
+            TargetArch::RiscV32 => "riscv32",
+            TargetArch::RiscV64 => "riscv64",
+            TargetArch::Mips => "mips",
+            TargetArch::Mips64 => "mips64",
+            TargetArch::PowerPC64 => "powerpc64",
+            TargetArch::S390x => "s390x",
+            TargetArch::Sparc64 => "sparc64",
+            TargetArch::Loong64 => "loongarch64",
+            TargetArch::Wasm32 => "wasm32",
+            TargetArch::Other(name) => name,
+        }
+    }
+
+    fn triple_str(&self) -> &'static str {
+        match self {
+            TargetArch::X86 => "i686",
+            TargetArch::RiscV64 => "riscv64gc",
+            arch => arch.as_str(),
+        }
+    }
+}
+
+impl Display for TargetArch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TargetArch {
+    type Err = ParseTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        Ok(match name.as_str() {
+            "x86" | "i386" | "i486" | "i586" | "i686" | "ia32" => TargetArch::X86,
+            "x86_64" | "x86-64" | "amd64" | "x64" => TargetArch::X86_64,
+            "aarch64" | "arm64" => TargetArch::AArch64,
+            "arm" | "armel" | "armhf" => TargetArch::Arm,
+            "mips" | "mipsel" => TargetArch::Mips,
+            "mips64" | "mips64el" => TargetArch::Mips64,
+            "powerpc64" | "powerpc64le" | "ppc64" | "ppc64le" => TargetArch::PowerPC64,
+            "s390x" => TargetArch::S390x,
+            "sparc64" | "sparcv9" => TargetArch::Sparc64,
+            "loongarch64" | "loong64" => TargetArch::Loong64,
+            "wasm32" => TargetArch::Wasm32,
+            name if name.starts_with("armv") || name.starts_with("thumbv") => TargetArch::Arm,
+            name if name.starts_with("riscv32") => TargetArch::RiscV32,
+            name if name.starts_with("riscv64") => TargetArch::RiscV64,
+            _ if s == TARGET_ARCH.as_str() => TARGET_ARCH,
+            _ => return parse_error("arch", s),
+        })
+    }
+}
+
+impl TargetSystem {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TargetSystem::Windows => "windows",
+            TargetSystem::Linux => "linux",
+            TargetSystem::MacOS => "macos",
+            TargetSystem::Android => "android",
+            TargetSystem::IOS => "ios",
+            TargetSystem::FreeBSD => "freebsd",
+            TargetSystem::DragonFly => "dragonfly",
+            TargetSystem::NetBSD => "netbsd",
+            TargetSystem::OpenBSD => "openbsd",
+            TargetSystem::Illumos => "illumos",
+            TargetSystem::Solaris => "solaris",
+            TargetSystem::Haiku => "haiku",
+            TargetSystem::Wasi => "wasi",
+            TargetSystem::BareMetal => "none",
+            TargetSystem::Other(name) => name,
+        }
+    }
+
+    fn triple_str(&self) -> &'static str {
+        match self {
+            TargetSystem::MacOS => "darwin",
+            system => system.as_str(),
+        }
+    }
+
+    fn default_vendor(&self) -> &'static str {
+        match self {
+            TargetSystem::MacOS | TargetSystem::IOS => "apple",
+            TargetSystem::Windows => "pc",
+            TargetSystem::Solaris => "sun",
+            TargetSystem::Android | TargetSystem::Wasi => "",
+            _ => "unknown",
+        }
+    }
+}
+
+impl Display for TargetSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TargetSystem {
+    type Err = ParseTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "windows" | "win32" | "win64" | "win" => TargetSystem::Windows,
+            "linux" => TargetSystem::Linux,
+            "macos" | "darwin" | "osx" | "macosx" => TargetSystem::MacOS,
+            "android" => TargetSystem::Android,
+            "ios" => TargetSystem::IOS,
+            "freebsd" => TargetSystem::FreeBSD,
+            "dragonfly" | "dragonflybsd" => TargetSystem::DragonFly,
+            "netbsd" => TargetSystem::NetBSD,
+            "openbsd" => TargetSystem::OpenBSD,
+            "illumos" => TargetSystem::Illumos,
+            "solaris" | "sunos" => TargetSystem::Solaris,
+            "haiku" => TargetSystem::Haiku,
+            "wasi" | "wasip1" | "wasip2" => TargetSystem::Wasi,
+            "none" | "baremetal" | "bare-metal" => TargetSystem::BareMetal,
+            _ if s == TARGET_SYSTEM.as_str() => TARGET_SYSTEM,
+            _ => return parse_error("system", s),
+        })
+    }
+}
+
+impl TargetEnv {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TargetEnv::Gnu => "gnu",
+            TargetEnv::Musl => "musl",
+            TargetEnv::Msvc => "msvc",
+            TargetEnv::None => "",
+            TargetEnv::Other(name) => name,
+        }
+    }
+}
+
+impl Display for TargetEnv {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TargetEnv {
+    type Err = ParseTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "gnu" | "glibc" => TargetEnv::Gnu,
+            "musl" => TargetEnv::Musl,
+            "msvc" => TargetEnv::Msvc,
+            "" | "none" => TargetEnv::None,
+            _ if s == TARGET_ENV.as_str() => TARGET_ENV,
+            _ => return parse_error("env", s),
+        })
+    }
+}
+
+const TARGET_ABIS: [&str; 6] = ["eabi", "eabihf", "elf", "sim", "macabi", "softfloat"];
+
+#[derive(Debug, Clone)]
+pub struct TargetTriple {
+    pub arch: TargetArch,
+    pub vendor: String,
+    pub system: TargetSystem,
+    pub env: TargetEnv,
+    pub abi: String,
+    arch_name: String,
+    system_name: String,
+}
+
+impl TargetTriple {
+    pub fn new(arch: TargetArch, system: TargetSystem, env: TargetEnv) -> Self {
+        Self {
+            arch,
+            vendor: system.default_vendor().to_string(),
+            system,
+            env,
+            abi: String::new(),
+            arch_name: arch.triple_str().to_string(),
+            system_name: system.triple_str().to_string(),
+        }
+    }
+
+    pub fn current() -> Self {
+        let little_endian = TARGET_ENDIAN == Endianness::Little;
+        let arch_name = match TARGET_ARCH {
+            TargetArch::X86 if !cfg!(target_feature = "sse2") => "i586",
+            TargetArch::Arm if cfg!(target_feature = "v7") => "armv7",
+            TargetArch::Mips if little_endian => "mipsel",
+            TargetArch::Mips64 if little_endian => "mips64el",
+            TargetArch::PowerPC64 if little_endian => "powerpc64le",
+            arch => arch.triple_str(),
+        };
+        let abi = match TARGET_ABIS.iter().find(|abi| cfg_target_abi(abi)) {
+            Some(abi) => *abi,
+            None if TARGET_SYSTEM == TargetSystem::BareMetal && matches!(TARGET_ARCH, TargetArch::RiscV32 | TargetArch::RiscV64) => "elf",
+            None => "",
+        };
+        let (system_name, env) = match (TARGET_SYSTEM, TARGET_ENV) {
+            (TargetSystem::Wasi, TargetEnv::Other(version)) => (format!("wasi{version}"), TargetEnv::None),
+            (system, TargetEnv::Other(env)) if env == abi => (system.triple_str().to_string(), TargetEnv::None),
+            (system, env) => (system.triple_str().to_string(), env),
+        };
+        let mut triple = Self::new(TARGET_ARCH, TARGET_SYSTEM, env).with_abi(abi);
+        if TARGET_SYSTEM == TargetSystem::BareMetal && TARGET_ARCH == TargetArch::Arm {
+            triple.vendor.clear();
+        }
+        triple.arch_name = arch_name.to_string();
+        triple.system_name = system_name;
+        triple
+    }
+
+    pub fn arch_name(&self) -> &str {
+        if self.arch_name.parse() == Ok(self.arch) {
+            return &self.arch_name
+        }
+        self.arch.triple_str()
+    }
+
+    pub fn system_name(&self) -> &str {
+        if self.system_name.parse() == Ok(self.system) {
+            return &self.system_name
+        }
+        self.system.triple_str()
+    }
+
+    pub fn with_vendor(mut self, vendor: impl Into<String>) -> Self {
+        self.vendor = vendor.into();
+        self
+    }
+
+    pub fn with_abi(mut self, abi: impl Into<String>) -> Self {
+        self.abi = abi.into();
+        self
+    }
+}
+
+fn cfg_target_abi(abi: &str) -> bool {
+    match abi {
+        "eabi" => cfg!(target_abi = "eabi"),
+        "eabihf" => cfg!(target_abi = "eabihf"),
+        "sim" => cfg!(target_abi = "sim"),
+        "macabi" => cfg!(target_abi = "macabi"),
+        "softfloat" => cfg!(target_abi = "softfloat"),
+        _ => false,
+    }
+}
+
+fn canonical_arch_name(name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    match name.as_str() {
+        "x86" | "ia32" => "i686",
+        "x86-64" | "amd64" | "x64" => "x86_64",
+        "arm64" => "aarch64",
+        "armel" | "armhf" => "arm",
+        "ppc64" => "powerpc64",
+        "ppc64le" => "powerpc64le",
+        "loong64" => "loongarch64",
+        "riscv64" => "riscv64gc",
+        name => name,
+    }.to_string()
+}
+
+fn canonical_system_name(name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    match name.as_str() {
+        "macos" | "osx" | "macosx" => "darwin",
+        "win32" | "win64" | "win" => "windows",
+        "dragonflybsd" => "dragonfly",
+        "sunos" => "solaris",
+        "baremetal" | "bare-metal" => "none",
+        name => name,
+    }.to_string()
+}
+
+impl PartialEq for TargetTriple {
+    fn eq(&self, other: &Self) -> bool {
+        (self.arch, &self.vendor, self.system, self.env, &self.abi) == (other.arch, &other.vendor, other.system, other.env, &other.abi)
+    }
+}
+
+impl Eq for TargetTriple {}
+
+impl Hash for TargetTriple {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.arch, &self.vendor, self.system, self.env, &self.abi).hash(state)
+    }
+}
+
+impl Display for TargetTriple {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.arch_name())?;
+        if !self.vendor.is_empty() {
+            write!(f, "-{}", self.vendor)?;
+        }
+        if self.system == TargetSystem::Android {
+            return write!(f, "-linux-android{}", self.abi)
+        }
+        write!(f, "-{}", self.system_name())?;
+        if self.env != TargetEnv::None || !self.abi.is_empty() {
+            write!(f, "-{}{}", self.env, self.abi)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TargetTriple {
+    type Err = ParseTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split('-').collect::<Vec<_>>();
+        let (arch, vendor, system, env) = match parts.as_slice() {
+            [arch, system] => (arch, "", *system, ""),
+            [arch, "linux", env] if env.starts_with("android") => (arch, "", *env, ""),
+            [arch, system, env] if *system != "unknown" && system.parse::<TargetSystem>().is_ok() => (arch, "", *system, *env),
+            [arch, vendor, system] => (arch, *vendor, *system, ""),
+            [arch, vendor, system, env] => (arch, *vendor, *system, *env),
+            _ => return parse_error("triple", s),
+        };
+        let (arch_name, system_name) = (canonical_arch_name(arch), canonical_system_name(system));
+        let arch = arch.parse()?;
+        let (system, abi) = match system {
+            "unknown" => (TargetSystem::Other("unknown"), ""),
+            "androideabi" => (TargetSystem::Android, "eabi"),
+            system => (system.parse()?, ""),
+        };
+        let (env, abi) = match ["gnu", "musl", "msvc"].iter().find(|prefix| env.starts_with(*prefix)) {
+            Some(prefix) => (prefix.parse()?, &env[prefix.len()..]),
+            None if !abi.is_empty() => (TargetEnv::None, abi),
+            None if TARGET_ABIS.contains(&env) => (TargetEnv::None, env),
+            None => (env.parse()?, ""),
+        };
+        Ok(Self { arch, vendor: vendor.to_string(), system, env, abi: abi.to_string(), arch_name, system_name })
+    }
+}
+
+#[cfg(test)]
+mod const_info_tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!("amd64".parse(), Ok(TargetArch::X86_64));
+        assert_eq!("ARM64".parse(), Ok(TargetArch::AArch64));
+        assert_eq!("armv7".parse(), Ok(TargetArch::Arm));
+        assert_eq!("darwin".parse(), Ok(TargetSystem::MacOS));
+        assert_eq!(TargetArch::Loong64.to_string(), "loongarch64");
+        assert_eq!(TargetSystem::BareMetal.to_string(), "none");
+        assert_eq!(TargetArch::Other("m68k").to_string(), "m68k");
+        assert_eq!(TARGET_ARCH.to_string().parse(), Ok(TARGET_ARCH));
+        assert_eq!(TARGET_SYSTEM.to_string().parse(), Ok(TARGET_SYSTEM));
+        assert_eq!(TARGET_ENV.to_string().parse(), Ok(TARGET_ENV));
+        assert_eq!("vax".parse::<TargetArch>().unwrap_err().to_string(), "unknown target arch `vax`");
+    }
+
+    #[test]
+    fn test_triple() {
+        for triple in [
+            "x86_64-unknown-linux-musl",
+            "x86_64-unknown-linux-gnu",
+            "aarch64-apple-darwin",
+            "x86_64-pc-windows-msvc",
+            "aarch64-linux-android",
+            "arm-unknown-linux-gnueabihf",
+            "wasm32-unknown-unknown",
+            "riscv64gc-unknown-linux-gnu",
+            "powerpc64le-unknown-linux-gnu",
+            "armv7-unknown-linux-gnueabihf",
+            "i586-unknown-linux-gnu",
+            "mipsel-unknown-linux-gnu",
+            "wasm32-wasip1",
+            "thumbv7em-none-eabihf",
+            "armv7a-none-eabi",
+            "riscv32imac-unknown-none-elf",
+            "aarch64-apple-ios-sim",
+        ] {
+            assert_eq!(triple.parse::<TargetTriple>().unwrap().to_string(), triple);
+        }
+
+        let triple = "armv7-linux-androideabi".parse::<TargetTriple>().unwrap();
+        assert_eq!((triple.arch, triple.system, triple.abi.as_str()), (TargetArch::Arm, TargetSystem::Android, "eabi"));
+        assert_eq!(triple.to_string(), "armv7-linux-androideabi");
+        assert_eq!(triple.arch_name(), "armv7");
+
+        let triple = "thumbv7em-none-eabihf".parse::<TargetTriple>().unwrap();
+        assert_eq!((triple.arch, triple.system, triple.env, triple.abi.as_str()), (TargetArch::Arm, TargetSystem::BareMetal, TargetEnv::None, "eabihf"));
+        let triple = "aarch64-apple-ios-sim".parse::<TargetTriple>().unwrap();
+        assert_eq!((triple.system, triple.env, triple.abi.as_str()), (TargetSystem::IOS, TargetEnv::None, "sim"));
+
+        let triple = "AMD64-unknown-linux-gnu".parse::<TargetTriple>().unwrap();
+        assert_eq!(triple, TargetTriple::new(TargetArch::X86_64, TargetSystem::Linux, TargetEnv::Gnu));
+        assert_eq!(triple.to_string(), "x86_64-unknown-linux-gnu");
+        assert_eq!("x86_64-apple-macosx".parse::<TargetTriple>().unwrap().to_string(), "x86_64-apple-darwin");
+
+        let mut triple = "powerpc64le-unknown-linux-gnu".parse::<TargetTriple>().unwrap();
+        assert_eq!(triple.arch, TargetArch::PowerPC64);
+        triple.arch = TargetArch::X86;
+        assert_eq!(triple.to_string(), "i686-unknown-linux-gnu");
+
+        let triple = "x86_64-unknown-linux-musl".parse::<TargetTriple>().unwrap();
+        assert_eq!(triple, TargetTriple::new(TargetArch::X86_64, TargetSystem::Linux, TargetEnv::Musl));
+        assert_eq!(TargetTriple::new(TargetArch::AArch64, TargetSystem::MacOS, TargetEnv::None).to_string(), "aarch64-apple-darwin");
+        let current = TargetTriple::current();
+        assert_eq!((current.arch, current.system), (TARGET_ARCH, TARGET_SYSTEM));
+        assert_eq!(current.to_string().parse(), Ok(current));
+        assert!("x86_64".parse::<TargetTriple>().is_err());
+        assert!("x86_64-unknown-plan9".parse::<TargetTriple>().is_err());
+    }
+}
@@ -0,0 +1,172 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::Read;
+use std::ops::RangeInclusive;
+use crate::{crc32, crc32_update, ReadExt, WriteExt};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ContainerHeader {
+    pub magic: [u8; 4],
+    pub version: u16,
+    pub flags: u16,
+}
+
+impl ContainerHeader {
+    pub fn new(magic: [u8; 4], version: u16) -> Self {
+        Self { magic, version, flags: 0 }
+    }
+
+    pub fn with_flags(mut self, flags: u16) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    fn to_bytes(self) -> [u8; 8] {
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&self.magic);
+        bytes[4..6].copy_from_slice(&self.version.to_be_bytes());
+        bytes[6..].copy_from_slice(&self.flags.to_be_bytes());
+        bytes
+    }
+}
+
+pub fn write_container<W: io::Write>(
+    writer: &mut W,
+    header: ContainerHeader,
+    write_payload: impl FnOnce(&mut Vec<u8>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut payload = Vec::new();
+    write_payload(&mut payload)?;
+    let header = header.to_bytes();
+    writer.write_all(&header)?;
+    writer.write_u64(payload.len() as u64)?;
+    writer.write_all(&payload)?;
+    writer.write_u32(crc32_update(crc32(&header), &payload))
+}
+
+pub fn read_container<R: io::Read, T>(
+    reader: &mut R,
+    magic: [u8; 4],
+    versions: RangeInclusive<u16>,
+    read_payload: impl FnOnce(ContainerHeader, &mut &[u8]) -> io::Result<T>,
+) -> Result<T, ContainerError> {
+    let mut found = [0u8; 4];
+    reader.read_exact(&mut found).map_err(ContainerError::Io)?;
+    if found != magic {
+        return Err(ContainerError::BadMagic { expected: magic, found })
+    }
+    let version = reader.read_u16().map_err(ContainerError::Io)?;
+    if !versions.contains(&version) {
+        return Err(ContainerError::UnsupportedVersion { supported: versions, found: version })
+    }
+    let flags = reader.read_u16().map_err(ContainerError::Io)?;
+    let header = ContainerHeader { magic, version, flags };
+    let len = reader.read_u64().map_err(ContainerError::Io)?;
+    let mut payload = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut payload).map_err(ContainerError::Io)?;
+    if payload.len() as u64 != len {
+        return Err(ContainerError::Io(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated payload")))
+    }
+    let expected = reader.read_u32().map_err(ContainerError::Io)?;
+    let actual = crc32_update(crc32(&header.to_bytes()), &payload);
+    if expected != actual {
+        return Err(ContainerError::ChecksumMismatch { expected, actual })
+    }
+    read_payload(header, &mut payload.as_slice()).map_err(ContainerError::Payload)
+}
+
+#[derive(Debug)]
+pub enum ContainerError {
+    Io(io::Error),
+    BadMagic { expected: [u8; 4], found: [u8; 4] },
+    UnsupportedVersion { supported: RangeInclusive<u16>, found: u16 },
+    ChecksumMismatch { expected: u32, actual: u32 },
+    Payload(io::Error),
+}
+
+impl Display for ContainerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContainerError::Io(e) | ContainerError::Payload(e) => Display::fmt(e, f),
+            ContainerError::BadMagic { expected, found } => {
+                write!(f, "bad magic: expected `{}`, found `{}`", expected.escape_ascii(), found.escape_ascii())
+            }
+            ContainerError::UnsupportedVersion { supported, found } => {
+                write!(f, "unsupported version {found} (supported {}..={})", supported.start(), supported.end())
+            }
+            ContainerError::ChecksumMismatch { expected, actual } => {
+                write!(f, "checksum mismatch: expected {expected:#010x}, got {actual:#010x}")
+            }
+        }
+    }
+}
+
+impl Error for ContainerError {}
+
+impl From<ContainerError> for io::Error {
+    fn from(value: ContainerError) -> Self {
+        match value {
+            ContainerError::Io(e) | ContainerError::Payload(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod container_tests {
+    use super::*;
+
+    const MAGIC: [u8; 4] = *b"FRTE";
+
+    fn sample() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_container(&mut bytes, ContainerHeader::new(MAGIC, 2).with_flags(1), |w| {
+            w.write_string_len16("payload")?;
+            w.write_u64(42)
+        }).unwrap();
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(ContainerHeader, String, u64), ContainerError> {
+        read_container(&mut &bytes[..], MAGIC, 1..=2, |header, r| {
+            Ok((header, r.read_string_len16()?, r.read_u64()?))
+        })
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (header, text, number) = decode(&sample()).unwrap();
+        assert_eq!(header, ContainerHeader::new(MAGIC, 2).with_flags(1));
+        assert_eq!(text, "payload");
+        assert_eq!(number, 42);
+    }
+
+    #[test]
+    fn test_failures() {
+        let mut bytes = sample();
+        bytes[..4].copy_from_slice(b"XXXX");
+        let err = decode(&bytes).err().unwrap();
+        assert!(matches!(err, ContainerError::BadMagic { .. }));
+        assert_eq!(err.to_string(), "bad magic: expected `FRTE`, found `XXXX`");
+
+        let mut bytes = sample();
+        bytes[5] = 3;
+        let err = decode(&bytes).err().unwrap();
+        assert!(matches!(err, ContainerError::UnsupportedVersion { found: 3, .. }));
+        assert!(err.to_string().starts_with("unsupported version 3 (supported "));
+
+        let mut bytes = sample();
+        bytes[7] = 0;
+        assert!(matches!(decode(&bytes), Err(ContainerError::ChecksumMismatch { .. })));
+
+        let mut bytes = sample();
+        bytes[20] ^= 0xFF;
+        let err = decode(&bytes).err().unwrap();
+        assert!(matches!(err, ContainerError::ChecksumMismatch { .. }));
+        assert!(err.to_string().starts_with("checksum mismatch: expected 0x"));
+
+        let bytes = sample();
+        assert!(matches!(decode(&bytes[..bytes.len() - 2]), Err(ContainerError::Io(_))));
+    }
+}
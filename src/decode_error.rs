@@ -0,0 +1,133 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use crate::BinaryCodec;
+
+#[derive(Debug)]
+pub struct DecodeError {
+    pub offset: u64,
+    pub expected: &'static str,
+    pub path: String,
+    pub source: io::Error,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to decode {} at offset {:#x}", self.expected, self.offset)?;
+        if !self.path.is_empty() {
+            write!(f, " ({})", self.path)?;
+        }
+        write!(f, ": {}", self.source)
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+impl From<DecodeError> for io::Error {
+    fn from(value: DecodeError) -> Self {
+        io::Error::new(value.source.kind(), value)
+    }
+}
+
+pub struct PositionReader<R> {
+    inner: R,
+    position: u64,
+    path: Vec<String>,
+}
+
+impl<R: io::Read> PositionReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, position: 0, path: Vec::new() }
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for segment in &self.path {
+            if !path.is_empty() && !segment.starts_with('[') {
+                path.push('.');
+            }
+            path.push_str(segment);
+        }
+        path
+    }
+
+    pub fn field<T>(
+        &mut self,
+        name: impl Into<String>,
+        decode: impl FnOnce(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        self.path.push(name.into());
+        let result = decode(self);
+        self.path.pop();
+        result
+    }
+
+    pub fn index<T>(
+        &mut self,
+        index: usize,
+        decode: impl FnOnce(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        self.field(format!("[{index}]"), decode)
+    }
+
+    pub fn expect<T>(
+        &mut self,
+        expected: &'static str,
+        read: impl FnOnce(&mut Self) -> io::Result<T>,
+    ) -> Result<T, DecodeError> {
+        let offset = self.position;
+        read(self).map_err(|source| DecodeError { offset, expected, path: self.path(), source })
+    }
+
+    pub fn read_as<T: BinaryCodec>(&mut self) -> Result<T, DecodeError> {
+        self.expect(std::any::type_name::<T>(), |r| T::decode(r))
+    }
+}
+
+impl<R: io::Read> io::Read for PositionReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod decode_error_tests {
+    use super::*;
+    use crate::{ReadExt, WriteExt};
+
+    #[test]
+    fn test_error_context() {
+        let mut bytes = Vec::new();
+        bytes.write_u32(2).unwrap();
+        bytes.write_string_len16("first").unwrap();
+        bytes.write_u16(10).unwrap();
+        bytes.extend_from_slice(b"sec");
+
+        let mut reader = PositionReader::new(bytes.as_slice());
+        let count = reader.field("count", |r| r.read_as::<u32>()).unwrap();
+        let err = reader.field("entries", |r| {
+            (0..count as usize)
+                .map(|i| r.index(i, |r| r.field("name", |r| r.expect("string_len16", |r| r.read_string_len16()))))
+                .collect::<Result<Vec<_>, _>>()
+        }).unwrap_err();
+        assert_eq!(err.offset, 11);
+        assert_eq!(err.expected, "string_len16");
+        assert_eq!(err.path, "entries[1].name");
+        assert_eq!(err.to_string(), "failed to decode string_len16 at offset 0xb (entries[1].name): failed to fill whole buffer");
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -1,18 +1,311 @@
-use std::collections::BTreeMap;
+use std::collections::{btree_map, hash_map, BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use fastrand::Rng;
+use crate::Bitmap;
 
 pub use fastrand;
 
+pub mod distribution;
+pub mod id;
+pub mod property;
+
+pub fn with_seed<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    struct Restore(u64);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            fastrand::seed(self.0);
+        }
+    }
+
+    let _restore = Restore(fastrand::get_seed());
+    fastrand::seed(seed);
+    f()
+}
+
+pub trait RandomKey: Sized {
+    fn random() -> Self;
+    fn random_with(rng: &mut Rng) -> Self;
+}
+
+impl RandomKey for u32 {
+    fn random() -> Self {
+        fastrand::u32(..)
+    }
+
+    fn random_with(rng: &mut Rng) -> Self {
+        rng.u32(..)
+    }
+}
+
+impl RandomKey for u64 {
+    fn random() -> Self {
+        fastrand::u64(0..u64::MAX)
+    }
+
+    fn random_with(rng: &mut Rng) -> Self {
+        rng.u64(0..u64::MAX)
+    }
+}
+
+impl RandomKey for u128 {
+    fn random() -> Self {
+        fastrand::u128(..)
+    }
+
+    fn random_with(rng: &mut Rng) -> Self {
+        rng.u128(..)
+    }
+}
+
+pub fn random_alphanumeric(len: usize) -> String {
+    (0..len).map(|_| fastrand::alphanumeric()).collect()
+}
+
+pub fn random_alphanumeric_with(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| rng.alphanumeric()).collect()
+}
+
 pub trait FastrandMapExt<K, V> {
-    fn insert_randomized(&mut self, value: V) -> K;
+    type VacantEntry<'a> where Self: 'a;
+
+    fn randomized_entry_by(&mut self, generate: impl FnMut() -> K) -> Self::VacantEntry<'_>;
+    fn insert_randomized_by(&mut self, generate: impl FnMut() -> K, value: V) -> K;
+
+    fn randomized_entry(&mut self) -> Self::VacantEntry<'_> where K: RandomKey {
+        self.randomized_entry_by(K::random)
+    }
+
+    fn insert_randomized(&mut self, value: V) -> K where K: RandomKey {
+        self.insert_randomized_by(K::random, value)
+    }
+
+    fn randomized_entry_with(&mut self, rng: &mut Rng) -> Self::VacantEntry<'_> where K: RandomKey {
+        self.randomized_entry_by(|| K::random_with(rng))
+    }
+
+    fn insert_randomized_with(&mut self, rng: &mut Rng, value: V) -> K where K: RandomKey {
+        self.insert_randomized_by(|| K::random_with(rng), value)
+    }
 }
 
-impl<V> FastrandMapExt<u64, V> for BTreeMap<u64, V> {
-    fn insert_randomized(&mut self, value: V) -> u64 {
-        let mut key = fastrand::u64(0..u64::MAX);
+impl<K: Ord + Clone, V> FastrandMapExt<K, V> for BTreeMap<K, V> {
+    type VacantEntry<'a> = btree_map::VacantEntry<'a, K, V> where Self: 'a;
+
+    fn randomized_entry_by(&mut self, mut generate: impl FnMut() -> K) -> Self::VacantEntry<'_> {
+        let mut key = generate();
         while self.contains_key(&key) {
-            key = fastrand::u64(0..u64::MAX);
+            key = generate();
         }
-        self.insert(key, value);
+        match self.entry(key) {
+            btree_map::Entry::Vacant(entry) => entry,
+            btree_map::Entry::Occupied(_) => unreachable!(),
+        }
+    }
+
+    fn insert_randomized_by(&mut self, generate: impl FnMut() -> K, value: V) -> K {
+        let entry = self.randomized_entry_by(generate);
+        let key = entry.key().clone();
+        entry.insert(value);
         key
     }
-}
\ No newline at end of file
+}
+
+impl<K: Hash + Eq + Clone, V> FastrandMapExt<K, V> for HashMap<K, V> {
+    type VacantEntry<'a> = hash_map::VacantEntry<'a, K, V> where Self: 'a;
+
+    fn randomized_entry_by(&mut self, mut generate: impl FnMut() -> K) -> Self::VacantEntry<'_> {
+        let mut key = generate();
+        while self.contains_key(&key) {
+            key = generate();
+        }
+        match self.entry(key) {
+            hash_map::Entry::Vacant(entry) => entry,
+            hash_map::Entry::Occupied(_) => unreachable!(),
+        }
+    }
+
+    fn insert_randomized_by(&mut self, generate: impl FnMut() -> K, value: V) -> K {
+        let entry = self.randomized_entry_by(generate);
+        let key = entry.key().clone();
+        entry.insert(value);
+        key
+    }
+}
+
+pub fn reservoir_sample<I: IntoIterator>(rng: &mut Rng, iter: I, k: usize) -> Vec<I::Item> {
+    let mut iter = iter.into_iter();
+    let mut reservoir = iter.by_ref().take(k).collect::<Vec<_>>();
+    if reservoir.len() < k {
+        return reservoir
+    }
+    for (i, item) in iter.enumerate() {
+        let j = rng.usize(..=k + i);
+        if j < k {
+            reservoir[j] = item;
+        }
+    }
+    reservoir
+}
+
+pub fn sample_indices(rng: &mut Rng, n: usize, k: usize) -> Vec<usize> {
+    assert!(k <= n, "cannot sample {k} indices out of {n}");
+    let mut chosen = HashSet::with_capacity(k);
+    let mut indices = Vec::with_capacity(k);
+    for j in n - k..n {
+        let t = rng.usize(..=j);
+        let pick = if chosen.insert(t) { t } else {
+            chosen.insert(j);
+            j
+        };
+        indices.push(pick);
+    }
+    rng.shuffle(&mut indices);
+    indices
+}
+
+pub fn random_subset(rng: &mut Rng, len: usize, probability: f64) -> Bitmap {
+    let mut bitmap = Bitmap::new(len);
+    for i in 0..len {
+        if rng.f64() < probability {
+            bitmap.set(i, true);
+        }
+    }
+    bitmap
+}
+
+pub struct AliasTable {
+    probability: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    pub fn new(weights: &[f64]) -> Option<Self> {
+        let total = weights.iter().sum::<f64>();
+        if weights.is_empty() || !total.is_finite() || total <= 0.0 || weights.iter().any(|&w| w < 0.0) {
+            return None
+        }
+        let n = weights.len();
+        let mut scaled = weights.iter().map(|&w| w * n as f64 / total).collect::<Vec<_>>();
+        let mut probability = vec![1.0; n];
+        let mut alias = (0..n).collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..n).partition(|&i| scaled[i] < 1.0);
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            probability[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] -= 1.0 - scaled[s];
+            if scaled[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        Some(Self { probability, alias })
+    }
+
+    pub fn len(&self) -> usize {
+        self.alias.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.alias.is_empty()
+    }
+
+    pub fn sample(&self, rng: &mut Rng) -> usize {
+        let i = rng.usize(..self.alias.len());
+        if rng.f64() < self.probability[i] { i } else { self.alias[i] }
+    }
+}
+
+#[cfg(test)]
+mod fastrand_ext_tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_randomized() {
+        let mut btree = BTreeMap::<u32, usize>::new();
+        let mut hash = HashMap::<u128, usize>::new();
+        for i in 0..100 {
+            let key = btree.insert_randomized(i);
+            assert_eq!(btree[&key], i);
+            let key = hash.insert_randomized(i);
+            assert_eq!(hash[&key], i);
+        }
+        assert_eq!(btree.len(), 100);
+        assert_eq!(hash.len(), 100);
+
+        let mut strings = HashMap::new();
+        let key = strings.insert_randomized_by(|| random_alphanumeric(12), ());
+        assert_eq!(key.len(), 12);
+        assert!(key.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        let mut small = BTreeMap::new();
+        let mut next = [1u8, 1, 2].into_iter();
+        small.insert(1u8, "taken");
+        assert_eq!(small.insert_randomized_by(|| next.next().unwrap(), "fresh"), 2);
+    }
+
+    #[test]
+    fn test_randomized_entry() {
+        let mut map = BTreeMap::<u64, (u64, &str)>::new();
+        let entry = map.randomized_entry();
+        let key = *entry.key();
+        entry.insert((key, "self-referencing"));
+        assert_eq!(map[&key].0, key);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let run = |seed| {
+            let mut rng = Rng::with_seed(seed);
+            let mut map = BTreeMap::<u64, usize>::new();
+            let keys = (0..10).map(|i| map.insert_randomized_with(&mut rng, i)).collect::<Vec<_>>();
+            (keys, random_alphanumeric_with(&mut rng, 8))
+        };
+        assert_eq!(run(7), run(7));
+        assert_ne!(run(7), run(8));
+
+        let global = |seed| with_seed(seed, || {
+            let mut map = HashMap::<u32, ()>::new();
+            (0..10).map(|_| map.insert_randomized(())).collect::<Vec<_>>()
+        });
+        assert_eq!(global(42), global(42));
+    }
+
+    #[test]
+    fn test_sampling() {
+        let mut rng = Rng::with_seed(1);
+        assert_eq!(reservoir_sample(&mut rng, 0..3, 5), [0, 1, 2]);
+        let sample = reservoir_sample(&mut rng, 0..1000, 10);
+        assert_eq!(sample.len(), 10);
+        assert_eq!(sample.iter().collect::<HashSet<_>>().len(), 10);
+
+        for (n, k) in [(10, 10), (100, 5), (5, 0)] {
+            let indices = sample_indices(&mut rng, n, k);
+            assert_eq!(indices.len(), k);
+            assert_eq!(indices.iter().collect::<HashSet<_>>().len(), k);
+            assert!(indices.iter().all(|&i| i < n));
+        }
+
+        let subset = random_subset(&mut rng, 1000, 0.25);
+        let count = (0..1000).filter(|&i| subset.get(i)).count();
+        assert!((200..300).contains(&count));
+    }
+
+    #[test]
+    fn test_alias_table() {
+        assert!(AliasTable::new(&[]).is_none());
+        assert!(AliasTable::new(&[0.0, 0.0]).is_none());
+        assert!(AliasTable::new(&[1.0, -1.0]).is_none());
+
+        let mut rng = Rng::with_seed(2);
+        let table = AliasTable::new(&[1.0, 0.0, 3.0, 6.0]).unwrap();
+        let mut counts = [0usize; 4];
+        for _ in 0..100_000 {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        for (count, expected) in counts.iter().zip([0.1, 0.0, 0.3, 0.6]) {
+            assert!((*count as f64 / 100_000.0 - expected).abs() < 0.01);
+        }
+    }
+}
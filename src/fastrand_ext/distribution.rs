@@ -0,0 +1,319 @@
+use std::f64::consts::PI;
+use fastrand::Rng;
+
+pub trait Distribution<T> {
+    fn sample(&self, rng: &mut Rng) -> T;
+
+    fn sample_iter<'a>(&'a self, rng: &'a mut Rng) -> impl Iterator<Item=T> + 'a where Self: Sized {
+        std::iter::repeat_with(move || self.sample(rng))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Normal {
+    mean: f64,
+    std_dev: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, std_dev: f64) -> Option<Self> {
+        (mean.is_finite() && std_dev.is_finite() && std_dev >= 0.0).then_some(Self { mean, std_dev })
+    }
+
+    pub fn standard() -> Self {
+        Self { mean: 0.0, std_dev: 1.0 }
+    }
+}
+
+impl Distribution<f64> for Normal {
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        let u1 = 1.0 - rng.f64();
+        let u2 = rng.f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
+        self.mean + self.std_dev * z
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    pub fn new(rate: f64) -> Option<Self> {
+        (rate.is_finite() && rate > 0.0).then_some(Self { rate })
+    }
+}
+
+impl Distribution<f64> for Exponential {
+    fn sample(&self, rng: &mut Rng) -> f64 {
+        -(1.0 - rng.f64()).ln() / self.rate
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Poisson {
+    lambda: f64,
+}
+
+impl Poisson {
+    pub fn new(lambda: f64) -> Option<Self> {
+        (lambda.is_finite() && lambda > 0.0).then_some(Self { lambda })
+    }
+
+    fn sample_knuth(&self, rng: &mut Rng) -> u64 {
+        let limit = (-self.lambda).exp();
+        let mut product = rng.f64();
+        let mut k = 0;
+        while product > limit {
+            product *= rng.f64();
+            k += 1;
+        }
+        k
+    }
+
+    // Hörmann's transformed rejection with squeeze (PTRS), for large lambda.
+    fn sample_ptrs(&self, rng: &mut Rng) -> u64 {
+        let lambda = self.lambda;
+        let log_lambda = lambda.ln();
+        let b = 0.931 + 2.53 * lambda.sqrt();
+        let a = -0.059 + 0.02483 * b;
+        let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+        let v_r = 0.9277 - 3.6224 / (b - 2.0);
+        loop {
+            let u = rng.f64() - 0.5;
+            let v = rng.f64();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+            if us >= 0.07 && v <= v_r {
+                return k as u64
+            }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                continue
+            }
+            let lhs = v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln();
+            if lhs <= -lambda + k * log_lambda - ln_factorial(k as u64) {
+                return k as u64
+            }
+        }
+    }
+}
+
+impl Distribution<u64> for Poisson {
+    fn sample(&self, rng: &mut Rng) -> u64 {
+        if self.lambda < 30.0 {
+            self.sample_knuth(rng)
+        } else {
+            self.sample_ptrs(rng)
+        }
+    }
+}
+
+fn ln_factorial(k: u64) -> f64 {
+    if k < 16 {
+        return (2..=k).map(|i| (i as f64).ln()).sum()
+    }
+    let k = k as f64;
+    (k + 0.5) * k.ln() - k + 0.5 * (2.0 * PI).ln() + 1.0 / (12.0 * k) - 1.0 / (360.0 * k * k * k)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Binomial {
+    trials: u64,
+    probability: f64,
+}
+
+impl Binomial {
+    pub fn new(trials: u64, probability: f64) -> Option<Self> {
+        (0.0..=1.0).contains(&probability).then_some(Self { trials, probability })
+    }
+
+    // Counts successes by skipping geometrically distributed runs of failures, for small n * p.
+    fn sample_geometric(n: u64, p: f64, rng: &mut Rng) -> u64 {
+        let log_q = (1.0 - p).ln();
+        let mut successes = 0;
+        let mut position = 0u64;
+        loop {
+            let gap = ((1.0 - rng.f64()).ln() / log_q).floor();
+            if gap >= (n - position) as f64 {
+                return successes
+            }
+            position += gap as u64 + 1;
+            successes += 1;
+        }
+    }
+
+    // Hörmann's transformed rejection with squeeze (BTRS), for large n * p.
+    fn sample_btrs(n: u64, p: f64, rng: &mut Rng) -> u64 {
+        let q = 1.0 - p;
+        let spq = (n as f64 * p * q).sqrt();
+        let b = 1.15 + 2.53 * spq;
+        let a = -0.0873 + 0.0248 * b + 0.01 * p;
+        let c = n as f64 * p + 0.5;
+        let v_r = 0.92 - 4.2 / b;
+        let alpha = (2.83 + 5.1 / b) * spq;
+        let log_pq = (p / q).ln();
+        let m = ((n + 1) as f64 * p).floor() as u64;
+        let h = ln_factorial(m) + ln_factorial(n - m);
+        loop {
+            let u = rng.f64() - 0.5;
+            let v = rng.f64();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + c).floor();
+            if k < 0.0 || k > n as f64 {
+                continue
+            }
+            if us >= 0.07 && v <= v_r {
+                return k as u64
+            }
+            let k = k as u64;
+            let lhs = (v * alpha / (a / (us * us) + b)).ln();
+            if lhs <= h - ln_factorial(k) - ln_factorial(n - k) + (k as f64 - m as f64) * log_pq {
+                return k
+            }
+        }
+    }
+}
+
+impl Distribution<u64> for Binomial {
+    fn sample(&self, rng: &mut Rng) -> u64 {
+        let flipped = self.probability > 0.5;
+        let p = if flipped { 1.0 - self.probability } else { self.probability };
+        let successes = if p == 0.0 {
+            0
+        } else if self.trials as f64 * p < 10.0 {
+            Self::sample_geometric(self.trials, p, rng)
+        } else {
+            Self::sample_btrs(self.trials, p, rng)
+        };
+        if flipped { self.trials - successes } else { successes }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Zipf {
+    n: u64,
+    exponent: f64,
+    h_integral_x1: f64,
+    h_integral_n: f64,
+    s: f64,
+}
+
+impl Zipf {
+    pub fn new(n: u64, exponent: f64) -> Option<Self> {
+        if n == 0 || !exponent.is_finite() || exponent <= 0.0 {
+            return None
+        }
+        let mut zipf = Self { n, exponent, h_integral_x1: 0.0, h_integral_n: 0.0, s: 0.0 };
+        zipf.h_integral_x1 = zipf.h_integral(1.5) - 1.0;
+        zipf.h_integral_n = zipf.h_integral(n as f64 + 0.5);
+        zipf.s = 2.0 - zipf.h_integral_inverse(zipf.h_integral(2.5) - zipf.h(2.0));
+        Some(zipf)
+    }
+
+    fn h(&self, x: f64) -> f64 {
+        (-self.exponent * x.ln()).exp()
+    }
+
+    fn h_integral(&self, x: f64) -> f64 {
+        let log_x = x.ln();
+        helper_exp_m1((1.0 - self.exponent) * log_x) * log_x
+    }
+
+    fn h_integral_inverse(&self, x: f64) -> f64 {
+        let t = (x * (1.0 - self.exponent)).max(-1.0);
+        (helper_ln_1p(t) * x).exp()
+    }
+}
+
+fn helper_ln_1p(x: f64) -> f64 {
+    if x.abs() > 1e-8 { x.ln_1p() / x } else { 1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x)) }
+}
+
+fn helper_exp_m1(x: f64) -> f64 {
+    if x.abs() > 1e-8 { x.exp_m1() / x } else { 1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x)) }
+}
+
+impl Distribution<u64> for Zipf {
+    // Rejection-inversion sampling after Hörmann and Derflinger.
+    fn sample(&self, rng: &mut Rng) -> u64 {
+        loop {
+            let u = self.h_integral_n + rng.f64() * (self.h_integral_x1 - self.h_integral_n);
+            let x = self.h_integral_inverse(u);
+            let k = (x + 0.5).floor().clamp(1.0, self.n as f64);
+            if k - x <= self.s || u >= self.h_integral(k + 0.5) - self.h(k) {
+                return k as u64
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod distribution_tests {
+    use super::*;
+
+    const SAMPLES: usize = 200_000;
+
+    fn moments(samples: impl Iterator<Item=f64>) -> (f64, f64) {
+        let samples = samples.take(SAMPLES).collect::<Vec<_>>();
+        let mean = samples.iter().sum::<f64>() / SAMPLES as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (SAMPLES - 1) as f64;
+        (mean, variance)
+    }
+
+    fn assert_moments(samples: impl Iterator<Item=f64>, mean: f64, variance: f64) {
+        let (actual_mean, actual_variance) = moments(samples);
+        let mean_tolerance = 5.0 * (variance / SAMPLES as f64).sqrt();
+        assert!((actual_mean - mean).abs() < mean_tolerance, "mean {actual_mean} != {mean}");
+        assert!((actual_variance / variance - 1.0).abs() < 0.03, "variance {actual_variance} != {variance}");
+    }
+
+    #[test]
+    fn test_continuous() {
+        let mut rng = Rng::with_seed(10);
+        assert_moments(Normal::new(3.0, 2.0).unwrap().sample_iter(&mut rng), 3.0, 4.0);
+        assert_moments(Normal::standard().sample_iter(&mut rng), 0.0, 1.0);
+        assert_moments(Exponential::new(2.0).unwrap().sample_iter(&mut rng), 0.5, 0.25);
+        assert!(Normal::new(0.0, -1.0).is_none());
+        assert!(Exponential::new(0.0).is_none());
+    }
+
+    #[test]
+    fn test_poisson() {
+        let mut rng = Rng::with_seed(11);
+        for lambda in [0.5, 4.0, 29.0, 30.0, 250.0] {
+            let poisson = Poisson::new(lambda).unwrap();
+            assert_moments(poisson.sample_iter(&mut rng).map(|x| x as f64), lambda, lambda);
+        }
+    }
+
+    #[test]
+    fn test_binomial() {
+        let mut rng = Rng::with_seed(12);
+        for (n, p) in [(50, 0.3), (10_000, 0.6), (20, 0.05), (40, 0.2)] {
+            let binomial = Binomial::new(n, p).unwrap();
+            let mean = n as f64 * p;
+            assert_moments(binomial.sample_iter(&mut rng).map(|x| x as f64), mean, mean * (1.0 - p));
+        }
+        assert_eq!(Binomial::new(7, 1.0).unwrap().sample(&mut rng), 7);
+        assert_eq!(Binomial::new(7, 0.0).unwrap().sample(&mut rng), 0);
+        assert!(Binomial::new(7, 1.5).is_none());
+    }
+
+    #[test]
+    fn test_zipf() {
+        let mut rng = Rng::with_seed(13);
+        for (n, exponent) in [(10, 1.5), (100, 1.0), (5, 0.5)] {
+            let weights = (1..=n).map(|k| (k as f64).powf(-exponent)).collect::<Vec<_>>();
+            let total = weights.iter().sum::<f64>();
+            let mean = weights.iter().enumerate().map(|(i, w)| (i + 1) as f64 * w).sum::<f64>() / total;
+            let variance = weights.iter().enumerate()
+                .map(|(i, w)| ((i + 1) as f64 - mean).powi(2) * w)
+                .sum::<f64>() / total;
+            let zipf = Zipf::new(n, exponent).unwrap();
+            assert!(zipf.sample_iter(&mut rng).take(1000).all(|k| (1..=n).contains(&k)));
+            assert_moments(zipf.sample_iter(&mut rng).map(|x| x as f64), mean, variance);
+        }
+        assert!(Zipf::new(0, 1.0).is_none());
+    }
+}
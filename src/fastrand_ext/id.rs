@@ -0,0 +1,297 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use fastrand::Rng;
+use crate::RandomKey;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0
+    }
+
+    pub const fn as_u128(self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    pub fn version(self) -> u8 {
+        self.0[6] >> 4
+    }
+
+    pub fn new_v4(rng: &mut Rng) -> Self {
+        Self::from_parts(4, rng.u128(..))
+    }
+
+    fn from_parts(version: u8, value: u128) -> Self {
+        let mut bytes = value.to_be_bytes();
+        bytes[6] = (bytes[6] & 0x0F) | (version << 4);
+        bytes[8] = (bytes[8] & 0x3F) | 0x80;
+        Self(bytes)
+    }
+}
+
+impl Display for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Uuid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Uuid({self})")
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = if s.len() == 36 {
+            for (i, c) in s.char_indices() {
+                if matches!(i, 8 | 13 | 18 | 23) != (c == '-') {
+                    return Err(IdParseError::InvalidCharacter(c))
+                }
+            }
+            s.replace('-', "")
+        } else if s.len() == 32 {
+            s.to_string()
+        } else {
+            return Err(IdParseError::InvalidLength(s.len()))
+        };
+        let mut value = 0u128;
+        for c in hex.chars() {
+            let digit = c.to_digit(16).ok_or(IdParseError::InvalidCharacter(c))?;
+            value = value << 4 | digit as u128;
+        }
+        Ok(Self(value.to_be_bytes()))
+    }
+}
+
+impl RandomKey for Uuid {
+    fn random() -> Self {
+        Self::from_parts(4, fastrand::u128(..))
+    }
+
+    fn random_with(rng: &mut Rng) -> Self {
+        Self::new_v4(rng)
+    }
+}
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    pub const fn as_u128(self) -> u128 {
+        self.0
+    }
+
+    pub const fn timestamp_ms(self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+}
+
+impl Display for Ulid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut chars = [0u8; 26];
+        for (i, c) in chars.iter_mut().enumerate() {
+            *c = CROCKFORD[(self.0 >> (125 - 5 * i) & 0x1F) as usize];
+        }
+        f.write_str(std::str::from_utf8(&chars).unwrap())
+    }
+}
+
+impl Debug for Ulid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Ulid({self})")
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = IdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 26 {
+            return Err(IdParseError::InvalidLength(s.len()))
+        }
+        let mut value = 0u128;
+        for (i, c) in s.chars().enumerate() {
+            let digit = match c.to_ascii_uppercase() {
+                'O' => 0,
+                'I' | 'L' => 1,
+                c => CROCKFORD.iter().position(|&x| x as char == c).ok_or(IdParseError::InvalidCharacter(c))?,
+            };
+            if i == 0 && digit > 7 {
+                return Err(IdParseError::Overflow)
+            }
+            value = value << 5 | digit as u128;
+        }
+        Ok(Self(value))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum IdParseError {
+    InvalidLength(usize),
+    InvalidCharacter(char),
+    Overflow,
+}
+
+impl Display for IdParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdParseError::InvalidLength(len) => write!(f, "invalid length {len}"),
+            IdParseError::InvalidCharacter(c) => write!(f, "invalid character {c:?}"),
+            IdParseError::Overflow => f.write_str("ULID overflows 128 bits"),
+        }
+    }
+}
+
+impl Error for IdParseError {}
+
+pub struct IdGenerator {
+    rng: Rng,
+    monotonic: bool,
+    last_v7: Option<u128>,
+    last_ulid: Option<u128>,
+}
+
+impl IdGenerator {
+    pub fn new() -> Self {
+        Self::with_rng(Rng::with_seed(fastrand::u64(..)))
+    }
+
+    pub fn with_rng(rng: Rng) -> Self {
+        Self { rng, monotonic: false, last_v7: None, last_ulid: None }
+    }
+
+    pub fn monotonic(mut self, monotonic: bool) -> Self {
+        self.monotonic = monotonic;
+        self
+    }
+
+    pub fn uuid_v4(&mut self) -> Uuid {
+        Uuid::new_v4(&mut self.rng)
+    }
+
+    pub fn uuid_v7(&mut self) -> Uuid {
+        self.uuid_v7_at(now_ms())
+    }
+
+    pub fn uuid_v7_at(&mut self, timestamp_ms: u64) -> Uuid {
+        let value = next_id(&mut self.rng, self.monotonic, &mut self.last_v7, timestamp_ms, 74);
+        let (timestamp, rand_a, rand_b) = (value >> 74, value >> 62 & 0xFFF, value & ((1 << 62) - 1));
+        Uuid::from_parts(7, timestamp << 80 | rand_a << 64 | rand_b)
+    }
+
+    pub fn ulid(&mut self) -> Ulid {
+        self.ulid_at(now_ms())
+    }
+
+    pub fn ulid_at(&mut self, timestamp_ms: u64) -> Ulid {
+        Ulid(next_id(&mut self.rng, self.monotonic, &mut self.last_ulid, timestamp_ms, 80))
+    }
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+}
+
+// Packs a 48-bit timestamp above `random_bits` random bits. In monotonic mode an ID in the same
+// or an earlier millisecond is the previous one plus one, carrying into the timestamp if needed.
+fn next_id(rng: &mut Rng, monotonic: bool, last: &mut Option<u128>, timestamp_ms: u64, random_bits: u32) -> u128 {
+    let timestamp = (timestamp_ms & ((1 << 48) - 1)) as u128;
+    let value = match *last {
+        Some(last) if monotonic && last >> random_bits >= timestamp => last + 1,
+        _ => timestamp << random_bits | rng.u128(..) & ((1 << random_bits) - 1),
+    };
+    *last = Some(value);
+    value
+}
+
+#[cfg(test)]
+mod id_tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid() {
+        let mut generator = IdGenerator::with_rng(Rng::with_seed(1));
+        let v4 = generator.uuid_v4();
+        assert_eq!(v4.version(), 4);
+        assert_eq!(v4.to_bytes()[8] & 0xC0, 0x80);
+        let text = v4.to_string();
+        assert_eq!(text.len(), 36);
+        assert_eq!(text.parse::<Uuid>().unwrap(), v4);
+        assert_eq!(text.replace('-', "").to_uppercase().parse::<Uuid>().unwrap(), v4);
+
+        let v7 = generator.uuid_v7_at(0x0123_4567_89AB);
+        assert_eq!(v7.version(), 7);
+        assert!(v7.to_string().starts_with("01234567-89ab-7"));
+        assert_eq!(Uuid::from_bytes(v7.to_bytes()), v7);
+
+        assert_eq!("nope".parse::<Uuid>(), Err(IdParseError::InvalidLength(4)));
+        assert_eq!("01234567-89ab-cdef-0123+456789abcdef".parse::<Uuid>(), Err(IdParseError::InvalidCharacter('+')));
+    }
+
+    #[test]
+    fn test_ulid() {
+        assert_eq!(Ulid(0).to_string(), "00000000000000000000000000");
+        assert_eq!(Ulid(u128::MAX).to_string(), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!("7zzzzzzzzzzzzzzzzzzzzzzzzz".parse::<Ulid>().unwrap(), Ulid(u128::MAX));
+        assert_eq!("8ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Ulid>(), Err(IdParseError::Overflow));
+        assert_eq!(IdParseError::InvalidLength(4).to_string(), "invalid length 4");
+        assert_eq!(IdParseError::InvalidCharacter('+').to_string(), "invalid character '+'");
+        assert_eq!(IdParseError::Overflow.to_string(), "ULID overflows 128 bits");
+        assert_eq!("0000000000000000000000000O".parse::<Ulid>(), Ok(Ulid(0)));
+
+        let mut generator = IdGenerator::with_rng(Rng::with_seed(2));
+        let ulid = generator.ulid_at(1_700_000_000_000);
+        assert_eq!(ulid.timestamp_ms(), 1_700_000_000_000);
+        assert_eq!(ulid.to_string().parse::<Ulid>().unwrap(), ulid);
+        assert_eq!(Ulid::from_bytes(ulid.to_bytes()), ulid);
+    }
+
+    #[test]
+    fn test_monotonic() {
+        let mut generator = IdGenerator::with_rng(Rng::with_seed(3)).monotonic(true);
+        let ulids = (0..100).map(|_| generator.ulid_at(1000)).collect::<Vec<_>>();
+        assert!(ulids.windows(2).all(|w| w[0] < w[1]));
+        assert!(generator.ulid_at(999) > ulids[99]);
+
+        let uuids = (0..100).map(|_| generator.uuid_v7_at(1000)).collect::<Vec<_>>();
+        assert!(uuids.windows(2).all(|w| w[0] < w[1] && w[0].to_string() < w[1].to_string()));
+        assert!(uuids.iter().all(|u| u.version() == 7));
+
+        let mut generator = IdGenerator::with_rng(Rng::with_seed(3));
+        generator.last_ulid = Some(1000 << 80 | ((1 << 80) - 1));
+        assert_eq!(generator.monotonic(true).ulid_at(1000).timestamp_ms(), 1001);
+    }
+}
@@ -0,0 +1,423 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use fastrand::Rng;
+use crate::Bitmap;
+
+pub trait Arbitrary: Sized + Clone {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self;
+
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng, _size: usize) -> Self {
+        rng.bool()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self { vec![false] } else { Vec::new() }
+    }
+}
+
+macro_rules! arbitrary_unsigned {
+    ($($t:ident),*) => {$(
+        impl Arbitrary for $t {
+            fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+                match rng.u8(..10) {
+                    0 => [0, 1, $t::MAX][rng.usize(..3)],
+                    1..=4 => rng.$t(..),
+                    _ => rng.$t(..=$t::try_from(size).unwrap_or($t::MAX)),
+                }
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = Vec::new();
+                if *self != 0 {
+                    candidates.push(0);
+                }
+                let mut step = *self / 2;
+                while step != 0 {
+                    candidates.push(*self - step);
+                    step /= 2;
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+arbitrary_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! arbitrary_signed {
+    ($($t:ident),*) => {$(
+        impl Arbitrary for $t {
+            fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+                let bound = $t::try_from(size).unwrap_or($t::MAX);
+                match rng.u8(..10) {
+                    0 => [0, 1, -1, $t::MIN, $t::MAX][rng.usize(..5)],
+                    1..=4 => rng.$t(..),
+                    _ => rng.$t(-bound..=bound),
+                }
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = Vec::new();
+                if *self != 0 {
+                    candidates.push(0);
+                }
+                if *self < 0 && *self != $t::MIN {
+                    candidates.push(-*self);
+                }
+                let mut step = *self / 2;
+                while step != 0 {
+                    candidates.push(*self - step);
+                    step /= 2;
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+arbitrary_signed!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! arbitrary_float {
+    ($($t:ident),*) => {$(
+        impl Arbitrary for $t {
+            fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+                match rng.u8(..10) {
+                    0 => [0.0, -0.0, 1.0, -1.0, $t::EPSILON, $t::MIN, $t::MAX][rng.usize(..7)],
+                    _ => (rng.$t() * 2.0 - 1.0) * size as $t,
+                }
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = Vec::new();
+                if *self != 0.0 {
+                    candidates.push(0.0);
+                }
+                if self.trunc() != *self && self.trunc() != 0.0 {
+                    candidates.push(self.trunc());
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+arbitrary_float!(f32, f64);
+
+impl Arbitrary for char {
+    fn arbitrary(rng: &mut Rng, _size: usize) -> Self {
+        match rng.u8(..10) {
+            0..=6 => rng.char(' '..='~'),
+            _ => rng.char(..),
+        }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        if *self == 'a' { Vec::new() } else { vec!['a'] }
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        let len = rng.usize(..=size);
+        (0..len).map(|_| char::arbitrary(rng, size)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        self.chars().collect::<Vec<_>>().shrink().into_iter().map(|chars| chars.into_iter().collect()).collect()
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        let len = rng.usize(..=size);
+        (0..len).map(|_| T::arbitrary(rng, size)).collect()
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+        if self.is_empty() {
+            return candidates
+        }
+        candidates.push(Vec::new());
+        let mut chunk = self.len() / 2;
+        while chunk > 0 {
+            for start in (0..self.len()).step_by(chunk) {
+                let mut shorter = self[..start].to_vec();
+                shorter.extend_from_slice(&self[(start + chunk).min(self.len())..]);
+                if !shorter.is_empty() {
+                    candidates.push(shorter);
+                }
+            }
+            chunk /= 2;
+        }
+        for (i, item) in self.iter().enumerate() {
+            for smaller in item.shrink() {
+                let mut vec = self.clone();
+                vec[i] = smaller;
+                candidates.push(vec);
+            }
+        }
+        candidates
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        if rng.u8(..4) == 0 { None } else { Some(T::arbitrary(rng, size)) }
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        match self {
+            None => Vec::new(),
+            Some(value) => std::iter::once(None).chain(value.shrink().into_iter().map(Some)).collect(),
+        }
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        (A::arbitrary(rng, size), B::arbitrary(rng, size))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b) = self;
+        let mut candidates = a.shrink().into_iter().map(|a| (a, b.clone())).collect::<Vec<_>>();
+        candidates.extend(b.shrink().into_iter().map(|b| (a.clone(), b)));
+        candidates
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary, C: Arbitrary> Arbitrary for (A, B, C) {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        (A::arbitrary(rng, size), B::arbitrary(rng, size), C::arbitrary(rng, size))
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let (a, b, c) = self;
+        let mut candidates = a.shrink().into_iter().map(|a| (a, b.clone(), c.clone())).collect::<Vec<_>>();
+        candidates.extend(b.shrink().into_iter().map(|b| (a.clone(), b, c.clone())));
+        candidates.extend(c.shrink().into_iter().map(|c| (a.clone(), b.clone(), c)));
+        candidates
+    }
+}
+
+impl Arbitrary for Bitmap {
+    fn arbitrary(rng: &mut Rng, size: usize) -> Self {
+        let len = rng.usize(..=size);
+        let probability = rng.f64();
+        let mut bitmap = Bitmap::new(len);
+        for i in 0..len {
+            bitmap.set(i, rng.f64() < probability);
+        }
+        bitmap
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let bits = (0..self.len()).map(|i| self.get(i)).collect::<Vec<_>>();
+        let mut candidates = Vec::new();
+        let mut len = self.len() / 2;
+        loop {
+            candidates.push(bitmap_from_bits(&bits[..len]));
+            if len + 1 >= self.len() {
+                break
+            }
+            len = (len + self.len()) / 2;
+        }
+        candidates.retain(|bitmap| bitmap.len() < self.len());
+        for i in (0..bits.len()).filter(|&i| bits[i]) {
+            let mut cleared = bits.clone();
+            cleared[i] = false;
+            candidates.push(bitmap_from_bits(&cleared));
+        }
+        candidates
+    }
+}
+
+fn bitmap_from_bits(bits: &[bool]) -> Bitmap {
+    let mut bitmap = Bitmap::new(bits.len());
+    for (i, &bit) in bits.iter().enumerate() {
+        bitmap.set(i, bit);
+    }
+    bitmap
+}
+
+pub trait PropertyResult {
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl PropertyResult for bool {
+    fn into_result(self) -> Result<(), String> {
+        if self { Ok(()) } else { Err("property returned false".into()) }
+    }
+}
+
+impl PropertyResult for () {
+    fn into_result(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<E: Display> PropertyResult for Result<(), E> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckFailure<T> {
+    pub seed: u64,
+    pub iteration: usize,
+    pub original: T,
+    pub minimal: T,
+    pub shrinks: usize,
+    pub message: String,
+}
+
+impl<T: Debug> Display for CheckFailure<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "property failed at iteration {} (seed {:#x}): {}", self.iteration, self.seed, self.message)?;
+        writeln!(f, "original input: {:?}", self.original)?;
+        write!(f, "minimal input after {} shrinks: {:?}", self.shrinks, self.minimal)
+    }
+}
+
+impl<T: Debug> Error for CheckFailure<T> {}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Check {
+    iterations: usize,
+    seed: u64,
+    max_size: usize,
+    max_shrinks: usize,
+}
+
+impl Check {
+    pub fn new(iterations: usize) -> Self {
+        Self { iterations, seed: fastrand::u64(..), max_size: 100, max_shrinks: 1000 }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn with_max_shrinks(mut self, max_shrinks: usize) -> Self {
+        self.max_shrinks = max_shrinks;
+        self
+    }
+
+    pub fn run<T: Arbitrary, R: PropertyResult>(&self, mut property: impl FnMut(&T) -> R) -> Result<(), CheckFailure<T>> {
+        let mut rng = Rng::with_seed(self.seed);
+        for iteration in 0..self.iterations {
+            let size = if self.iterations <= 1 { self.max_size } else { iteration * self.max_size / (self.iterations - 1) };
+            let input = T::arbitrary(&mut rng, size);
+            if let Err(message) = property(&input).into_result() {
+                let (minimal, message, shrinks) = self.shrink(input.clone(), message, &mut property);
+                return Err(CheckFailure { seed: self.seed, iteration, original: input, minimal, shrinks, message })
+            }
+        }
+        Ok(())
+    }
+
+    fn shrink<T: Arbitrary, R: PropertyResult>(
+        &self,
+        mut current: T,
+        mut message: String,
+        property: &mut impl FnMut(&T) -> R,
+    ) -> (T, String, usize) {
+        let mut shrinks = 0;
+        let mut attempts = 0;
+        'outer: while attempts < self.max_shrinks {
+            for candidate in current.shrink() {
+                attempts += 1;
+                if let Err(e) = property(&candidate).into_result() {
+                    current = candidate;
+                    message = e;
+                    shrinks += 1;
+                    continue 'outer
+                }
+                if attempts >= self.max_shrinks {
+                    break 'outer
+                }
+            }
+            break
+        }
+        (current, message, shrinks)
+    }
+}
+
+pub fn check<T: Arbitrary + Debug, R: PropertyResult>(iterations: usize, property: impl FnMut(&T) -> R) {
+    if let Err(failure) = Check::new(iterations).run(property) {
+        panic!("{failure}\nreproduce with Check::new({iterations}).with_seed({:#x})", failure.seed);
+    }
+}
+
+#[cfg(test)]
+mod property_tests {
+    use super::*;
+    use crate::BinaryCodec;
+
+    #[test]
+    fn test_passing() {
+        check(200, |v: &Vec<u32>| {
+            let mut sorted = v.clone();
+            sorted.sort();
+            let mut again = sorted.clone();
+            again.sort();
+            again == sorted && sorted.windows(2).all(|w| w[0] <= w[1])
+        });
+        check(200, |value: &(String, Option<i64>, Vec<u16>)| {
+            let mut bytes = Vec::new();
+            value.encode(&mut bytes).map_err(|e| e.to_string())?;
+            let decoded = <(String, Option<i64>, Vec<u16>)>::decode(&mut bytes.as_slice()).map_err(|e| e.to_string())?;
+            if decoded == *value { Ok(()) } else { Err(format!("decoded {decoded:?}")) }
+        });
+        check(100, |b: &Bitmap| {
+            let inverse = b.inverse();
+            inverse.inverse() == *b && (0..b.len()).all(|i| inverse.get(i) != b.get(i))
+        });
+    }
+
+    #[test]
+    fn test_shrinking() {
+        let failure = Check::new(500).with_seed(1).run(|&n: &u64| n < 1000).unwrap_err();
+        assert_eq!(failure.minimal, 1000);
+        assert!(failure.original >= 1000);
+
+        let failure = Check::new(500).with_seed(2).run(|&n: &i32| n > -50).unwrap_err();
+        assert_eq!(failure.minimal, -50);
+
+        let failure = Check::new(500).with_seed(3).run(|v: &Vec<u8>| v.iter().all(|&x| x < 10)).unwrap_err();
+        assert_eq!(failure.minimal, [10]);
+
+        let failure = Check::new(500).with_seed(4)
+            .run(|v: &Vec<u32>| if v.len() < 3 { Ok(()) } else { Err(format!("len {}", v.len())) })
+            .unwrap_err();
+        assert_eq!(failure.minimal, [0, 0, 0]);
+        assert_eq!(failure.message, "len 3");
+        assert!(failure.to_string().contains("minimal input after"));
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let run = |seed| Check::new(500).with_seed(seed).run(|s: &String| !s.contains('x')).unwrap_err();
+        let (first, second) = (run(5), run(5));
+        assert_eq!((first.iteration, first.original), (second.iteration, second.original));
+        assert_eq!(first.minimal, "x");
+
+        let failure = Check::new(500).with_seed(6).run(|b: &Bitmap| (0..b.len()).filter(|&i| b.get(i)).count() < 2).unwrap_err();
+        assert_eq!((0..failure.minimal.len()).filter(|&i| failure.minimal.get(i)).count(), 2);
+        assert!(failure.minimal.len() <= failure.original.len());
+    }
+}
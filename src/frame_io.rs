@@ -0,0 +1,188 @@
+use std::io;
+use crate::{crc32, ReadExt, WriteExt};
+
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+pub struct FrameWriter<W> {
+    inner: W,
+    checksum: bool,
+    max_frame_size: usize,
+}
+
+impl<W: io::Write> FrameWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, checksum: false, max_frame_size: DEFAULT_MAX_FRAME_SIZE }
+    }
+
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size.min(u32::MAX as usize);
+        self
+    }
+
+    pub fn write_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        if frame.len() > self.max_frame_size {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Frame too large"));
+        }
+        self.inner.write_u32(frame.len() as u32)?;
+        self.inner.write_all(frame)?;
+        if self.checksum {
+            self.inner.write_u32(crc32(frame))?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+pub struct FrameReader<R> {
+    inner: R,
+    checksum: bool,
+    max_frame_size: usize,
+}
+
+impl<R: io::Read> FrameReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, checksum: false, max_frame_size: DEFAULT_MAX_FRAME_SIZE }
+    }
+
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size.min(u32::MAX as usize);
+        self
+    }
+
+    pub fn read_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut len = [0u8; 4];
+        let mut filled = 0;
+        while filled < len.len() {
+            match self.inner.read(&mut len[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated frame header")),
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let len = u32::from_be_bytes(len) as usize;
+        if len > self.max_frame_size {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Frame too large"));
+        }
+        let mut frame = vec![0u8; len];
+        self.inner.read_exact(&mut frame).map_err(truncated)?;
+        if self.checksum {
+            let expected = self.inner.read_u32().map_err(truncated)?;
+            if crc32(&frame) != expected {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Frame checksum mismatch"));
+            }
+        }
+        Ok(Some(frame))
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> Iterator for FrameReader<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+fn truncated(e: io::Error) -> io::Error {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        io::Error::new(io::ErrorKind::UnexpectedEof, "Truncated frame")
+    } else {
+        e
+    }
+}
+
+#[cfg(test)]
+mod frame_io_tests {
+    use super::*;
+
+    fn encode(frames: &[&[u8]], checksum: bool) -> Vec<u8> {
+        let mut writer = FrameWriter::new(Vec::new()).with_checksum(checksum);
+        for frame in frames {
+            writer.write_frame(frame).unwrap();
+        }
+        writer.into_inner()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for checksum in [false, true] {
+            let bytes = encode(&[b"hello", b"", b"world"], checksum);
+            let mut reader = FrameReader::new(bytes.as_slice()).with_checksum(checksum);
+            assert_eq!(reader.read_frame().unwrap().unwrap(), b"hello");
+            assert_eq!(reader.read_frame().unwrap().unwrap(), b"");
+            assert_eq!(reader.read_frame().unwrap().unwrap(), b"world");
+            assert!(reader.read_frame().unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_truncated() {
+        let bytes = encode(&[b"hello"], true);
+        for cut in 1..bytes.len() {
+            let mut reader = FrameReader::new(&bytes[..cut]).with_checksum(true);
+            let err = reader.read_frame().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        }
+    }
+
+    #[test]
+    fn test_checksum_mismatch() {
+        let mut bytes = encode(&[b"hello"], true);
+        bytes[5] ^= 1;
+        let mut reader = FrameReader::new(bytes.as_slice()).with_checksum(true);
+        assert_eq!(reader.read_frame().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_max_frame_size() {
+        let mut writer = FrameWriter::new(Vec::new()).with_max_frame_size(4);
+        assert_eq!(writer.write_frame(b"hello").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        let bytes = encode(&[b"hello"], false);
+        let mut reader = FrameReader::new(bytes.as_slice()).with_max_frame_size(4);
+        assert_eq!(reader.read_frame().unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+}
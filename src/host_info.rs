@@ -0,0 +1,193 @@
+use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+use crate::{Endianness, TargetArch, TargetSystem, TARGET_ARCH, TARGET_SYSTEM};
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct CpuFeatures {
+    pub sse4_2: bool,
+    pub avx2: bool,
+    pub avx512f: bool,
+    pub neon: bool,
+}
+
+impl CpuFeatures {
+    pub fn detect() -> Self {
+        let mut features = Self::default();
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            features.sse4_2 = std::arch::is_x86_feature_detected!("sse4.2");
+            features.avx2 = std::arch::is_x86_feature_detected!("avx2");
+            features.avx512f = std::arch::is_x86_feature_detected!("avx512f");
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            features.neon = std::arch::is_aarch64_feature_detected!("neon");
+        }
+        #[cfg(target_arch = "arm")]
+        {
+            features.neon = cfg!(target_feature = "neon");
+        }
+        features
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        [(self.sse4_2, "sse4.2"), (self.avx2, "avx2"), (self.avx512f, "avx512f"), (self.neon, "neon")]
+            .into_iter()
+            .filter_map(|(enabled, name)| enabled.then_some(name))
+            .collect()
+    }
+}
+
+impl Display for CpuFeatures {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names = self.names();
+        if names.is_empty() {
+            return f.write_str("none")
+        }
+        f.write_str(&names.join(" "))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct HostInfo {
+    pub arch: TargetArch,
+    pub system: TargetSystem,
+    pub features: CpuFeatures,
+    pub logical_cores: usize,
+    pub physical_cores: Option<usize>,
+    pub cache_line_size: usize,
+    pub page_size: usize,
+    pub endianness: Endianness,
+    pub pointer_width: u32,
+}
+
+impl HostInfo {
+    pub fn detect() -> Self {
+        Self {
+            arch: TARGET_ARCH,
+            system: TARGET_SYSTEM,
+            features: CpuFeatures::detect(),
+            logical_cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            physical_cores: physical_cores(),
+            cache_line_size: cache_line_size(),
+            page_size: page_size(),
+            endianness: runtime_endianness(),
+            pointer_width: usize::BITS,
+        }
+    }
+
+    pub fn get() -> &'static Self {
+        static HOST_INFO: OnceLock<HostInfo> = OnceLock::new();
+        HOST_INFO.get_or_init(Self::detect)
+    }
+}
+
+impl Display for HostInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}, {}-bit {:?} endian, {} logical cores",
+               self.arch, self.system, self.pointer_width, self.endianness, self.logical_cores)?;
+        if let Some(physical) = self.physical_cores {
+            write!(f, " ({physical} physical)")?;
+        }
+        write!(f, ", {}B cache line, {}B page, features: {}", self.cache_line_size, self.page_size, self.features)
+    }
+}
+
+fn runtime_endianness() -> Endianness {
+    if 1u16.to_ne_bytes()[0] == 1 { Endianness::Little } else { Endianness::Big }
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn sysctl_usize(name: &std::ffi::CStr) -> Option<usize> {
+    let mut value: libc::c_int = 0;
+    let mut size = size_of::<libc::c_int>();
+    // SAFETY: `value` and `size` describe a valid, writable buffer for an integer-valued sysctl.
+    let result = unsafe {
+        libc::sysctlbyname(name.as_ptr(), (&raw mut value).cast(), &mut size, std::ptr::null_mut(), 0)
+    };
+    (result == 0 && value > 0).then_some(value as usize)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_usize(path: &str) -> Option<usize> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn physical_cores() -> Option<usize> {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+    let mut cores = std::collections::HashSet::new();
+    let mut physical_id = "";
+    for line in cpuinfo.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        match key.trim() {
+            "physical id" => physical_id = value.trim(),
+            "core id" => { cores.insert((physical_id, value.trim())); }
+            _ => {}
+        }
+    }
+    (!cores.is_empty()).then_some(cores.len())
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn physical_cores() -> Option<usize> {
+    sysctl_usize(c"hw.physicalcpu")
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios")))]
+fn physical_cores() -> Option<usize> {
+    None
+}
+
+fn cache_line_size() -> usize {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let detected = read_usize("/sys/devices/system/cpu/cpu0/cache/index0/coherency_line_size");
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    let detected = sysctl_usize(c"hw.cachelinesize");
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios")))]
+    let detected: Option<usize> = None;
+    detected.filter(|&size| size > 0).unwrap_or(match TARGET_ARCH {
+        TargetArch::AArch64 if cfg!(target_vendor = "apple") => 128,
+        _ => 64,
+    })
+}
+
+fn page_size() -> usize {
+    #[cfg(unix)]
+    // SAFETY: sysconf has no preconditions and only reads system configuration.
+    let detected = usize::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok();
+    #[cfg(not(unix))]
+    let detected: Option<usize> = None;
+    detected.filter(|&size| size > 0).unwrap_or(match TARGET_ARCH {
+        TargetArch::AArch64 if cfg!(target_vendor = "apple") => 16384,
+        _ => 4096,
+    })
+}
+
+#[cfg(test)]
+mod host_info_tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let host = HostInfo::get();
+        assert_eq!(host.arch, TARGET_ARCH);
+        assert_eq!(host.pointer_width as usize, size_of::<*const ()>() * 8);
+        assert_eq!(host.endianness == Endianness::Little, cfg!(target_endian = "little"));
+        assert!(host.logical_cores >= 1);
+        assert!(host.physical_cores.is_none_or(|n| n >= 1));
+        assert!(host.cache_line_size.is_power_of_two());
+        assert!(host.page_size.is_power_of_two());
+        if cfg!(target_feature = "avx2") {
+            assert!(host.features.avx2);
+        }
+        assert!(host.to_string().contains("features: "));
+    }
+
+    #[test]
+    fn test_feature_names() {
+        let features = CpuFeatures { sse4_2: true, avx2: true, ..Default::default() };
+        assert_eq!(features.to_string(), "sse4.2 avx2");
+        assert_eq!(CpuFeatures::default().to_string(), "none");
+    }
+}
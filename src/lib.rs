@@ -1,10 +1,22 @@
 mod bitmap;
 mod arg_sort;
 mod binary_io;
+#[cfg(feature = "async")]
+mod async_binary_io;
+mod frame_io;
+mod container;
+mod binary_codec;
+mod bit_io;
+#[cfg(feature = "mmap")]
+mod record_file;
+mod trace_io;
+mod decode_error;
 mod order_map;
 #[cfg(feature = "fastrand")]
 mod fastrand_ext;
 mod const_info;
+mod host_info;
+mod build_info;
 mod dyn_error;
 mod download;
 mod unix_kill;
@@ -16,11 +28,23 @@ mod wrapper;
 
 pub use arg_sort::*;
 pub use binary_io::*;
+#[cfg(feature = "async")]
+pub use async_binary_io::*;
+pub use frame_io::*;
+pub use container::*;
+pub use binary_codec::*;
+pub use bit_io::*;
+#[cfg(feature = "mmap")]
+pub use record_file::*;
+pub use trace_io::*;
+pub use decode_error::*;
 pub use bitmap::*;
 pub use order_map::*;
 #[cfg(feature = "fastrand")]
 pub use fastrand_ext::*;
 pub use const_info::*;
+pub use host_info::*;
+pub use build_info::*;
 pub use dyn_error::*;
 pub use download::*;
 pub use unix_kill::*;
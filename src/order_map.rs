@@ -1,37 +1,182 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::Peekable;
 use std::ops::Index;
-use crate::{VecApplyOrder, VecArgSort};
+use crate::{Keyed, VecApplyOrder, VecArgSort};
 
 pub fn order_with<T: Ord + ?Sized, U>(keys: &[&T], mut values: Vec<U>) -> Vec<U> {
-    let order = keys.arg_sort();
+    let order = keys.arg_rank();
     values.apply_order(order);
     values
 }
 
+pub fn align_to<T: Ord + ?Sized, U>(
+    reference: &[&T],
+    items: &mut Vec<U>,
+    key_fn: impl Fn(&U) -> &T,
+) -> AlignReport<U> {
+    let mut positions = BTreeMap::new();
+    for (i, &k) in reference.iter().enumerate() {
+        positions.entry(k).or_insert(i);
+    }
+    let mut slots = (0..reference.len()).map(|_| None).collect::<Vec<_>>();
+    let mut extra = Vec::new();
+    for item in items.drain(..) {
+        match positions.get(key_fn(&item)).copied() {
+            Some(i) if slots[i].is_none() => slots[i] = Some(item),
+            _ => extra.push(item),
+        }
+    }
+    let missing = slots.iter().enumerate().filter(|(_, x)| x.is_none()).map(|(i, _)| i).collect();
+    items.extend(slots.into_iter().flatten());
+    AlignReport { missing, extra }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AlignReport<U> {
+    pub missing: Vec<usize>,
+    pub extra: Vec<U>,
+}
+
+impl<U> AlignReport<U> {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
 pub fn order_map<'a, T: Ord + ?Sized>(keys: impl IntoIterator<Item=&'a T>) -> BTreeMap<&'a T, usize> {
-    let keys = keys.into_iter().collect::<Vec<_>>();
-    let order = keys.arg_sort();
-    keys.into_iter().zip(order).collect()
+    rank_map(&sorted_keys(keys.into_iter().collect()))
+}
+
+pub fn try_order_map<'a, T: Ord + ?Sized>(
+    keys: impl IntoIterator<Item=&'a T>
+) -> Result<BTreeMap<&'a T, usize>, OrderMapError<&'a T>> {
+    Ok(rank_map(&sorted_unique_keys(keys.into_iter().collect())?))
+}
+
+fn sorted_keys<K: Ord>(mut keys: Vec<K>) -> Vec<K> {
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+fn sorted_unique_keys<K: Ord>(mut keys: Vec<K>) -> Result<Vec<K>, OrderMapError<K>> {
+    keys.sort();
+    if let Some(i) = keys.windows(2).position(|w| w[0] == w[1]) {
+        return Err(OrderMapError::DuplicateKey(keys.swap_remove(i)))
+    }
+    Ok(keys)
+}
+
+fn rank_map<K: Ord + Clone>(sorted: &[K]) -> BTreeMap<K, usize> {
+    sorted.iter().cloned().enumerate().map(|(rank, k)| (k, rank)).collect()
+}
+
+fn reorder_by_rank<K, U>(
+    values: Vec<(K, U)>,
+    rank: impl Fn(&K) -> Option<usize>,
+    policy: UnknownKeyPolicy,
+) -> Result<Vec<U>, OrderMapError<K>> {
+    let mut known = Vec::with_capacity(values.len());
+    let mut unknown = Vec::new();
+    for (k, v) in values {
+        match rank(&k) {
+            Some(r) => known.push((r, v)),
+            None => match policy {
+                UnknownKeyPolicy::Drop => {}
+                UnknownKeyPolicy::Append => unknown.push(v),
+                UnknownKeyPolicy::Error => return Err(OrderMapError::UnknownKey(k)),
+            }
+        }
+    }
+    known.sort_by_key(|(r, _)| *r);
+    Ok(known.into_iter().map(|(_, v)| v).chain(unknown).collect())
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum UnknownKeyPolicy {
+    Drop,
+    Append,
+    Error,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OrderMapError<K> {
+    UnknownKey(K),
+    DuplicateKey(K),
+}
+
+impl<K: Debug> Display for OrderMapError<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderMapError::UnknownKey(key) => write!(f, "unknown key {key:?}"),
+            OrderMapError::DuplicateKey(key) => write!(f, "duplicate key {key:?}"),
+        }
+    }
 }
 
-pub struct OrderMap<'a, T: Ord + ?Sized>(BTreeMap<&'a T, usize>);
+impl<K: Debug> Error for OrderMapError<K> {}
+
+pub struct OrderMap<'a, T: Ord + ?Sized> {
+    ranks: BTreeMap<&'a T, usize>,
+    keys: Vec<&'a T>,
+}
 
 impl<'a, T: Ord + ?Sized> OrderMap<'a, T> {
     pub fn new(keys: impl IntoIterator<Item=&'a T>) -> Self {
-        let keys = keys.into_iter().collect::<Vec<_>>();
-        let order = keys.arg_sort();
-        Self(keys.into_iter().zip(order).collect())
+        let keys = sorted_keys(keys.into_iter().collect());
+        Self { ranks: rank_map(&keys), keys }
+    }
+
+    pub fn try_new(keys: impl IntoIterator<Item=&'a T>) -> Result<Self, OrderMapError<&'a T>> {
+        let keys = sorted_unique_keys(keys.into_iter().collect())?;
+        Ok(Self { ranks: rank_map(&keys), keys })
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn rank_of(&self, key: &T) -> Option<usize> {
+        self.ranks.get(key).copied()
+    }
+
+    pub fn key_at(&self, rank: usize) -> Option<&'a T> {
+        self.keys.get(rank).copied()
     }
 
     pub fn get(&self, key: &T) -> Option<usize> {
-        self.0.get(key).copied()
+        self.rank_of(key)
     }
 
     pub fn reorder<U>(&self, values: Vec<(&'a T, U)>) -> Vec<U> {
-        let order = values.iter().map(|(k, _)| self.0[k]).collect::<Vec<_>>();
-        let mut values = values.into_iter().map(|(_, v)| v).collect::<Vec<_>>();
-        values.apply_order(order);
-        values
+        let mut values = values.into_iter().map(|(k, v)| (self[k], v)).collect::<Vec<_>>();
+        values.sort_by_key(|(rank, _)| *rank);
+        values.into_iter().map(|(_, v)| v).collect()
+    }
+
+    pub fn try_reorder<U>(&self, values: Vec<(&'a T, U)>) -> Result<Vec<U>, OrderMapError<&'a T>> {
+        self.reorder_with(values, UnknownKeyPolicy::Error)
+    }
+
+    pub fn reorder_with<U>(
+        &self,
+        values: Vec<(&'a T, U)>,
+        policy: UnknownKeyPolicy,
+    ) -> Result<Vec<U>, OrderMapError<&'a T>> {
+        reorder_by_rank(values, |k| self.rank_of(k), policy)
+    }
+
+    pub fn to_owned_map(&self) -> OwnedOrderMap<T::Owned> where T: ToOwned, T::Owned: Ord + Clone {
+        let keys = self.keys.iter().map(|&k| k.to_owned()).collect::<Vec<_>>();
+        OwnedOrderMap { ranks: rank_map(&keys), keys }
     }
 }
 
@@ -39,6 +184,621 @@ impl<'a, T: Ord + ?Sized> Index<&'a T> for OrderMap<'a, T> {
     type Output = usize;
 
     fn index(&self, key: &'a T) -> &Self::Output {
-        self.0.get(key).unwrap()
+        self.ranks.get(key).expect("OrderMap index: key is not one of the map's keys")
+    }
+}
+
+pub struct OwnedOrderMap<T: Ord> {
+    ranks: BTreeMap<T, usize>,
+    keys: Vec<T>,
+}
+
+impl<T: Ord + Clone> OwnedOrderMap<T> {
+    pub fn new(keys: impl IntoIterator<Item=T>) -> Self {
+        let keys = sorted_keys(keys.into_iter().collect());
+        Self { ranks: rank_map(&keys), keys }
+    }
+
+    pub fn try_new(keys: impl IntoIterator<Item=T>) -> Result<Self, OrderMapError<T>> {
+        let keys = sorted_unique_keys(keys.into_iter().collect())?;
+        Ok(Self { ranks: rank_map(&keys), keys })
+    }
+
+    pub fn from_slice(keys: &[T]) -> Self {
+        Self::new(keys.iter().cloned())
+    }
+}
+
+impl<T: Ord> OwnedOrderMap<T> {
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn rank_of<Q: Ord + ?Sized>(&self, key: &Q) -> Option<usize> where T: Borrow<Q> {
+        self.ranks.get(key).copied()
+    }
+
+    pub fn key_at(&self, rank: usize) -> Option<&T> {
+        self.keys.get(rank)
+    }
+
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<usize> where T: Borrow<Q> {
+        self.rank_of(key)
+    }
+
+    pub fn reorder<Q: Ord + ?Sized, U>(&self, values: Vec<(&Q, U)>) -> Vec<U> where T: Borrow<Q> {
+        let mut values = values.into_iter().map(|(k, v)| (self[k], v)).collect::<Vec<_>>();
+        values.sort_by_key(|(rank, _)| *rank);
+        values.into_iter().map(|(_, v)| v).collect()
+    }
+
+    pub fn try_reorder<'q, Q: Ord + ?Sized, U>(
+        &self,
+        values: Vec<(&'q Q, U)>,
+    ) -> Result<Vec<U>, OrderMapError<&'q Q>> where T: Borrow<Q> {
+        self.reorder_with(values, UnknownKeyPolicy::Error)
+    }
+
+    pub fn reorder_with<'q, Q: Ord + ?Sized, U>(
+        &self,
+        values: Vec<(&'q Q, U)>,
+        policy: UnknownKeyPolicy,
+    ) -> Result<Vec<U>, OrderMapError<&'q Q>> where T: Borrow<Q> {
+        reorder_by_rank(values, |k| self.rank_of(*k), policy)
+    }
+}
+
+impl<T: Ord + Borrow<Q>, Q: Ord + ?Sized> Index<&Q> for OwnedOrderMap<T> {
+    type Output = usize;
+
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.ranks.get(key).expect("OwnedOrderMap index: key is not one of the map's keys")
+    }
+}
+
+pub struct IndexedMap<K: Ord + Clone, V> {
+    entries: Vec<(K, V)>,
+    indices: BTreeMap<K, usize>,
+}
+
+impl<K: Ord + Clone, V> IndexedMap<K, V> {
+    pub fn new() -> Self {
+        Self { entries: Vec::new(), indices: BTreeMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.indices.get(&key) {
+            Some(&i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.indices.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool where K: Borrow<Q> {
+        self.indices.contains_key(key)
+    }
+
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+        self.index_of(key).map(|i| &self.entries[i].1)
+    }
+
+    pub fn get_mut<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+        self.index_of(key).map(|i| &mut self.entries[i].1)
+    }
+
+    pub fn index_of<Q: Ord + ?Sized>(&self, key: &Q) -> Option<usize> where K: Borrow<Q> {
+        self.indices.get(key).copied()
+    }
+
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.entries.get(index).map(|(k, v)| (k, v))
+    }
+
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.entries.get_mut(index).map(|(k, v)| (&*k, v))
+    }
+
+    pub fn move_to<Q: Ord + ?Sized>(&mut self, key: &Q, position: usize) -> bool where K: Borrow<Q> {
+        let Some(from) = self.index_of(key) else {
+            return false
+        };
+        if position >= self.entries.len() {
+            return false
+        }
+        if from < position {
+            self.entries[from..=position].rotate_left(1);
+        } else {
+            self.entries[position..=from].rotate_right(1);
+        }
+        self.reindex(from.min(position)..=from.max(position));
+        true
+    }
+
+    pub fn swap_remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.indices.remove(key)?;
+        let (_, value) = self.entries.swap_remove(i);
+        self.reindex(i..self.entries.len().min(i + 1));
+        Some(value)
+    }
+
+    pub fn shift_remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+        let i = self.indices.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+        self.reindex(i..self.entries.len());
+        Some(value)
+    }
+
+    pub fn sort_keys(&mut self) {
+        self.entries.sort_by(|a, b| a.0.cmp(&b.0));
+        self.reindex(0..self.entries.len());
     }
-}
\ No newline at end of file
+
+    pub fn sort_values(&mut self) where V: Ord {
+        self.entries.sort_by(|a, b| a.1.cmp(&b.1));
+        self.reindex(0..self.entries.len());
+    }
+
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&K, &V, &K, &V) -> Ordering) {
+        self.entries.sort_by(|a, b| compare(&a.0, &a.1, &b.0, &b.1));
+        self.reindex(0..self.entries.len());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&K, &V)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=(&K, &mut V)> {
+        self.entries.iter_mut().map(|(k, v)| (&*k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item=&K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item=&V> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    fn reindex(&mut self, range: impl IntoIterator<Item=usize>) {
+        for i in range {
+            if let Some(index) = self.indices.get_mut(&self.entries[i].0) {
+                *index = i;
+            }
+        }
+    }
+}
+
+impl<K: Ord + Clone, V> Default for IndexedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V> FromIterator<(K, V)> for IndexedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item=(K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (k, v) in iter {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+impl<K: Ord + Clone, V> IntoIterator for IndexedMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = std::vec::IntoIter<(K, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<K: Ord + Clone + Borrow<Q>, Q: Ord + ?Sized, V> Index<&Q> for IndexedMap<K, V> {
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &Self::Output {
+        self.get(key).expect("IndexedMap index: key is not in the map")
+    }
+}
+
+impl<K: Ord + Clone, V> VecApplyOrder for IndexedMap<K, V> {
+    fn apply_order(&mut self, order: Vec<usize>) {
+        self.entries.apply_order(order);
+        self.reindex(0..self.entries.len());
+    }
+}
+
+pub fn merge_sorted<I: IntoIterator>(iterables: impl IntoIterator<Item=I>) -> MergeSorted<I::IntoIter, impl FnMut(&I::Item, &I::Item) -> Ordering>
+where I::Item: Ord {
+    merge_by(iterables, |a, b| a.cmp(b))
+}
+
+pub fn merge_by_key<I: IntoIterator, K: Ord>(
+    iterables: impl IntoIterator<Item=I>,
+    key_fn: impl Fn(&I::Item) -> K,
+) -> MergeSorted<I::IntoIter, impl FnMut(&I::Item, &I::Item) -> Ordering> {
+    merge_by(iterables, move |a, b| key_fn(a).cmp(&key_fn(b)))
+}
+
+pub fn merge_by<I: IntoIterator, F: FnMut(&I::Item, &I::Item) -> Ordering>(
+    iterables: impl IntoIterator<Item=I>,
+    compare: F,
+) -> MergeSorted<I::IntoIter, F> {
+    let mut sources = iterables.into_iter().map(IntoIterator::into_iter).collect::<Vec<_>>();
+    let heads = sources.iter_mut().map(Iterator::next).collect::<Vec<_>>();
+    let heap = (0..heads.len()).filter(|&i| heads[i].is_some()).collect();
+    let mut merge = MergeSorted { sources, heads, heap, compare };
+    for i in (0..merge.heap.len() / 2).rev() {
+        merge.sift_down(i);
+    }
+    merge
+}
+
+pub struct MergeSorted<I: Iterator, F> {
+    sources: Vec<I>,
+    heads: Vec<Option<I::Item>>,
+    heap: Vec<usize>,
+    compare: F,
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> Ordering> MergeSorted<I, F> {
+    // Min-heap of source indices ordered by their heads; ties go to the earlier source to keep the merge stable.
+    fn less(&mut self, a: usize, b: usize) -> bool {
+        let (x, y) = (self.heap[a], self.heap[b]);
+        let (Some(hx), Some(hy)) = (&self.heads[x], &self.heads[y]) else { unreachable!() };
+        (self.compare)(hx, hy).then(x.cmp(&y)) == Ordering::Less
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+            if left < self.heap.len() && self.less(left, smallest) {
+                smallest = left;
+            }
+            if right < self.heap.len() && self.less(right, smallest) {
+                smallest = right;
+            }
+            if smallest == i {
+                return
+            }
+            self.heap.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+impl<I: Iterator, F: FnMut(&I::Item, &I::Item) -> Ordering> Iterator for MergeSorted<I, F> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let source = *self.heap.first()?;
+        let next = self.sources[source].next();
+        let item = std::mem::replace(&mut self.heads[source], next);
+        if self.heads[source].is_none() {
+            self.heap.swap_remove(0);
+        }
+        self.sift_down(0);
+        item
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum JoinItem<L, R> {
+    Left(L),
+    Right(R),
+    Both(L, R),
+}
+
+pub fn sorted_join_by<L: IntoIterator, R: IntoIterator, F: FnMut(&L::Item, &R::Item) -> Ordering>(
+    left: L,
+    right: R,
+    compare: F,
+) -> SortedJoin<L::IntoIter, R::IntoIter, F> {
+    SortedJoin {
+        left: left.into_iter().peekable(),
+        right: right.into_iter().peekable(),
+        compare,
+        group: Vec::new(),
+        current: None,
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn sorted_join_by_key<L: IntoIterator, R: IntoIterator, K: Ord>(
+    left: L,
+    right: R,
+    left_key: impl Fn(&L::Item) -> K,
+    right_key: impl Fn(&R::Item) -> K,
+) -> SortedJoin<L::IntoIter, R::IntoIter, impl FnMut(&L::Item, &R::Item) -> Ordering> {
+    sorted_join_by(left, right, move |l, r| left_key(l).cmp(&right_key(r)))
+}
+
+pub type KeyedCompare<A, B, K> = fn(&Keyed<A, K>, &Keyed<B, K>) -> Ordering;
+
+pub fn sorted_join_keyed<A, B, K: Ord, L: IntoIterator<Item=Keyed<A, K>>, R: IntoIterator<Item=Keyed<B, K>>>(
+    left: L,
+    right: R,
+) -> SortedJoin<L::IntoIter, R::IntoIter, KeyedCompare<A, B, K>> {
+    sorted_join_by(left, right, |l, r| l.key().cmp(r.key()))
+}
+
+pub struct SortedJoin<L: Iterator, R: Iterator, F> {
+    left: Peekable<L>,
+    right: Peekable<R>,
+    compare: F,
+    group: Vec<R::Item>,
+    current: Option<(L::Item, usize)>,
+}
+
+impl<L: Iterator, R: Iterator, F: FnMut(&L::Item, &R::Item) -> Ordering> SortedJoin<L, R, F>
+where L::Item: Clone, R::Item: Clone {
+    pub fn inner(self) -> impl Iterator<Item=(L::Item, R::Item)> {
+        self.filter_map(|item| match item {
+            JoinItem::Both(l, r) => Some((l, r)),
+            _ => None,
+        })
+    }
+
+    pub fn left(self) -> impl Iterator<Item=(L::Item, Option<R::Item>)> {
+        self.filter_map(|item| match item {
+            JoinItem::Left(l) => Some((l, None)),
+            JoinItem::Both(l, r) => Some((l, Some(r))),
+            JoinItem::Right(_) => None,
+        })
+    }
+
+    pub fn outer(self) -> impl Iterator<Item=(Option<L::Item>, Option<R::Item>)> {
+        self.map(|item| match item {
+            JoinItem::Left(l) => (Some(l), None),
+            JoinItem::Right(r) => (None, Some(r)),
+            JoinItem::Both(l, r) => (Some(l), Some(r)),
+        })
+    }
+}
+
+// Runs of equal keys are joined many-to-many: the right run is buffered in `group` and every left item
+// with the same key is paired with each of its entries in turn.
+impl<L: Iterator, R: Iterator, F: FnMut(&L::Item, &R::Item) -> Ordering> Iterator for SortedJoin<L, R, F>
+where L::Item: Clone, R::Item: Clone {
+    type Item = JoinItem<L::Item, R::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((l, i)) = self.current.take() {
+                let r = self.group[i].clone();
+                if i + 1 == self.group.len() {
+                    return Some(JoinItem::Both(l, r))
+                }
+                self.current = Some((l.clone(), i + 1));
+                return Some(JoinItem::Both(l, r))
+            }
+            if let Some(first) = self.group.first() {
+                match self.left.peek() {
+                    Some(l) if (self.compare)(l, first) == Ordering::Equal => {
+                        self.current = Some((self.left.next()?, 0));
+                        continue
+                    }
+                    _ => self.group.clear(),
+                }
+            }
+            let ordering = match (self.left.peek(), self.right.peek()) {
+                (Some(l), Some(r)) => (self.compare)(l, r),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => return None,
+            };
+            match ordering {
+                Ordering::Less => return Some(JoinItem::Left(self.left.next()?)),
+                Ordering::Greater => return Some(JoinItem::Right(self.right.next()?)),
+                Ordering::Equal => {
+                    let l = self.left.next()?;
+                    while let Some(r) = self.right.next_if(|r| (self.compare)(&l, r) == Ordering::Equal) {
+                        self.group.push(r);
+                    }
+                    self.current = Some((l, 0));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod order_map_tests {
+    use super::*;
+    use crate::ToKeyed;
+
+    #[test]
+    fn test_rank_semantics() {
+        for keys in [["a", "b", "c", "d"], ["c", "a", "d", "b"], ["d", "c", "b", "a"]] {
+            let map = OrderMap::new(keys);
+            let owned = OwnedOrderMap::from_slice(&keys);
+            let ranks = order_map(keys);
+            for (rank, key) in ["a", "b", "c", "d"].into_iter().enumerate() {
+                assert_eq!(map.rank_of(key), Some(rank));
+                assert_eq!(map.key_at(rank), Some(key));
+                assert_eq!(owned.rank_of(key), Some(rank));
+                assert_eq!(owned.key_at(rank), Some(&key));
+                assert_eq!(ranks[key], rank);
+            }
+            assert_eq!(map.key_at(4), None);
+
+            let values = keys.map(|k| k.to_uppercase()).to_vec();
+            assert_eq!(order_with(&keys, values.clone()), ["A", "B", "C", "D"]);
+            let pairs = keys.into_iter().zip(values).collect::<Vec<_>>();
+            assert_eq!(map.reorder(pairs.clone()), ["A", "B", "C", "D"]);
+            assert_eq!(owned.reorder(pairs), ["A", "B", "C", "D"]);
+        }
+    }
+
+    #[test]
+    fn test_owned_matches_borrowed() {
+        let keys = ["b", "a", "c"];
+        let borrowed = OrderMap::new(keys);
+        let owned = OwnedOrderMap::new(keys.map(String::from));
+        for key in keys {
+            assert_eq!(owned.get(key), borrowed.get(key));
+            assert_eq!(owned[key], borrowed[key]);
+        }
+        assert_eq!(owned.reorder(vec![("a", 1), ("b", 2), ("c", 3)]), borrowed.reorder(vec![("a", 1), ("b", 2), ("c", 3)]));
+        assert_eq!(borrowed.to_owned_map().get("c"), owned.get("c"));
+
+        let boxed: OwnedOrderMap<Box<str>> = OwnedOrderMap::new(keys.map(Box::from));
+        assert_eq!(boxed.get("a"), owned.get("a"));
+        assert!(boxed.get("d").is_none());
+    }
+
+    #[test]
+    fn test_unknown_and_duplicate_keys() {
+        let map = OrderMap::new(["a", "b"]);
+        let values = vec![("b", 2), ("x", 0), ("a", 1)];
+        assert_eq!(map.try_reorder(values.clone()), Err(OrderMapError::UnknownKey("x")));
+        assert_eq!(map.reorder_with(values.clone(), UnknownKeyPolicy::Drop).unwrap(), [1, 2]);
+        assert_eq!(map.reorder_with(values, UnknownKeyPolicy::Append).unwrap(), [1, 2, 0]);
+        assert_eq!(map.try_reorder(vec![("b", 2), ("a", 1)]).unwrap(), [1, 2]);
+
+        let owned = OwnedOrderMap::new(["a", "b"].map(String::from));
+        assert_eq!(owned.try_reorder(vec![("c", 0)]).unwrap_err().to_string(), "unknown key \"c\"");
+
+        assert!(matches!(OrderMap::try_new(["a", "b", "a"]), Err(OrderMapError::DuplicateKey("a"))));
+        assert_eq!(try_order_map(["b", "b"]).unwrap_err(), OrderMapError::DuplicateKey("b"));
+        assert!(matches!(OwnedOrderMap::try_new([1, 2, 2]), Err(OrderMapError::DuplicateKey(2))));
+        assert_eq!(OwnedOrderMap::try_new([1, 2, 2]).err().unwrap().to_string(), "duplicate key 2");
+    }
+
+    #[test]
+    #[should_panic(expected = "OrderMap index: key is not one of the map's keys")]
+    fn test_index_unknown_key() {
+        let _ = OrderMap::new(["a", "b"])["c"];
+    }
+
+    #[test]
+    fn test_align_to() {
+        let mut items = vec![("c", 3), ("z", 0), ("a", 1), ("a", 9)];
+        let report = align_to(&["a", "b", "c"], &mut items, |item| item.0);
+        assert_eq!(items, [("a", 1), ("c", 3)]);
+        assert_eq!(report.missing, [1]);
+        assert_eq!(report.extra, [("z", 0), ("a", 9)]);
+        assert!(!report.is_complete());
+
+        let mut items = vec![(String::from("y"), 2), (String::from("x"), 1)];
+        assert!(align_to(&["x", "y"], &mut items, |item| item.0.as_str()).is_complete());
+        assert_eq!(items[0].1, 1);
+    }
+
+    #[test]
+    fn test_indexed_map() {
+        let mut map = ["d", "b", "a", "c"].into_iter().zip(1..).collect::<IndexedMap<_, _>>();
+        assert_eq!(map.insert("b", 20), Some(2));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["d", "b", "a", "c"]);
+        assert_eq!(map.get_index(1), Some((&"b", &20)));
+        assert_eq!(map.index_of("c"), Some(3));
+
+        assert!(map.move_to("c", 0));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["c", "d", "b", "a"]);
+        assert!(map.move_to("d", 3));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["c", "b", "a", "d"]);
+        assert_eq!(map.index_of("d"), Some(3));
+        assert!(!map.move_to("d", 4));
+        assert!(!map.move_to("x", 0));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["c", "b", "a", "d"]);
+
+        map.sort_keys();
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+        map.sort_values();
+        assert_eq!(map.values().copied().collect::<Vec<_>>(), [1, 3, 4, 20]);
+        assert_eq!(map.index_of("b"), Some(3));
+
+        map.apply_order(vec![3, 2, 1, 0]);
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["b", "c", "a", "d"]);
+        assert_eq!(map["a"], 3);
+
+        assert_eq!(map.swap_remove("b"), Some(20));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["d", "c", "a"]);
+        assert_eq!(map.shift_remove("c"), Some(4));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), ["d", "a"]);
+        assert_eq!(map.index_of("a"), Some(1));
+        assert_eq!(map.shift_remove("c"), None);
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let merged = merge_sorted([vec![1, 4, 7], vec![2, 5], vec![], vec![0, 3, 6, 8]]).collect::<Vec<_>>();
+        assert_eq!(merged, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let merged = merge_by_key([vec![("a", 1), ("c", 3)], vec![("b", 1), ("d", 2)]], |x| x.1)
+            .map(|x| x.0)
+            .collect::<Vec<_>>();
+        assert_eq!(merged, ["a", "b", "d", "c"]);
+
+        let keyed = merge_sorted([vec!["x".with_key(1), "z".with_key(3)], vec!["y".with_key(2)]])
+            .map(|x| *x.value())
+            .collect::<Vec<_>>();
+        assert_eq!(keyed, ["x", "y", "z"]);
+
+        let stable = merge_by_key([vec![(1, 'a'), (2, 'c')], vec![(1, 'b'), (2, 'd')], vec![(1, 'e')]], |x| x.0)
+            .map(|x| x.1)
+            .collect::<String>();
+        assert_eq!(stable, "abecd");
+
+        let sources = (0..50).map(|i| (i..1000).step_by(50).collect::<Vec<_>>());
+        assert!(merge_sorted(sources).eq(0..1000));
+    }
+
+    #[test]
+    fn test_sorted_joins() {
+        let left = [(1, "a"), (2, "b"), (4, "d")];
+        let right = [(2, 'B'), (3, 'C'), (4, 'D')];
+        let join = || sorted_join_by_key(left, right, |l| l.0, |r| r.0);
+        assert_eq!(join().inner().map(|(l, r)| (l.1, r.1)).collect::<Vec<_>>(), [("b", 'B'), ("d", 'D')]);
+        assert_eq!(
+            join().left().map(|(l, r)| (l.1, r.map(|r| r.1))).collect::<Vec<_>>(),
+            [("a", None), ("b", Some('B')), ("d", Some('D'))],
+        );
+        assert_eq!(
+            join().outer().map(|(l, r)| (l.map(|l| l.1), r.map(|r| r.1))).collect::<Vec<_>>(),
+            [(Some("a"), None), (Some("b"), Some('B')), (None, Some('C')), (Some("d"), Some('D'))],
+        );
+
+        let keyed = sorted_join_keyed(left.map(|(k, v)| v.with_key(k)), right.map(|(k, v)| v.with_key(k)))
+            .inner()
+            .map(|(l, r)| (*l.value(), *r.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(keyed, [("b", 'B'), ("d", 'D')]);
+    }
+
+    #[test]
+    fn test_sorted_join_duplicates() {
+        let left = [(1, 'a'), (1, 'b'), (2, 'c')];
+        let right = [(1, 'X'), (1, 'Y'), (3, 'Z')];
+        let join = || sorted_join_by_key(left, right, |l| l.0, |r| r.0);
+        assert_eq!(
+            join().inner().map(|(l, r)| (l.1, r.1)).collect::<Vec<_>>(),
+            [('a', 'X'), ('a', 'Y'), ('b', 'X'), ('b', 'Y')],
+        );
+        assert_eq!(
+            join().outer().map(|(l, r)| (l.map(|l| l.1), r.map(|r| r.1))).collect::<Vec<_>>(),
+            [
+                (Some('a'), Some('X')), (Some('a'), Some('Y')), (Some('b'), Some('X')), (Some('b'), Some('Y')),
+                (Some('c'), None), (None, Some('Z')),
+            ],
+        );
+        let one_right = sorted_join_by_key([(1, 'a'), (1, 'b')], [(1, 'X')], |l| l.0, |r| r.0);
+        assert_eq!(one_right.left().map(|(l, r)| (l.1, r.map(|r| r.1))).collect::<Vec<_>>(), [('a', Some('X')), ('b', Some('X'))]);
+    }
+}
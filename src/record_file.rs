@@ -0,0 +1,316 @@
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::ops::Deref;
+use std::path::Path;
+use memmap2::Mmap;
+use crate::{read_be_u64, ReadExt, WriteExt};
+
+pub struct MappedFile(Mmap);
+
+impl MappedFile {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only; callers must not truncate the file while it is mapped.
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Self(map))
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+pub struct FixedRecordReader {
+    file: MappedFile,
+    record_size: usize,
+}
+
+impl FixedRecordReader {
+    pub fn open(path: impl AsRef<Path>, record_size: usize) -> io::Result<Self> {
+        if record_size == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Record size must be non-zero"));
+        }
+        let file = MappedFile::open(path)?;
+        if !file.len().is_multiple_of(record_size) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "File size is not a multiple of record size"));
+        }
+        Ok(Self { file, record_size })
+    }
+
+    pub fn record_size(&self) -> usize {
+        self.record_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.file.len() / self.record_size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.file.is_empty()
+    }
+
+    pub fn record(&self, index: usize) -> Option<&[u8]> {
+        let start = index.checked_mul(self.record_size)?;
+        self.file.get(start..start.checked_add(self.record_size)?)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
+        self.file.chunks_exact(self.record_size)
+    }
+}
+
+pub struct IndexedRecordWriter {
+    data: BufWriter<File>,
+    index: BufWriter<File>,
+    offset: u64,
+    len: u64,
+}
+
+impl IndexedRecordWriter {
+    pub fn open(data_path: impl AsRef<Path>, index_path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with(data_path, index_path, false)
+    }
+
+    pub fn recover(data_path: impl AsRef<Path>, index_path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with(data_path, index_path, true)
+    }
+
+    fn open_with(data_path: impl AsRef<Path>, index_path: impl AsRef<Path>, recover: bool) -> io::Result<Self> {
+        let mut index = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(index_path)?;
+        let index_size = index.metadata()?.len();
+        if !index_size.is_multiple_of(8) {
+            if !recover {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Index size is not a multiple of 8"));
+            }
+            index.set_len(index_size / 8 * 8)?;
+        }
+        let mut data = OpenOptions::new().write(true).create(true).truncate(false).open(data_path)?;
+        let data_size = data.metadata()?.len();
+        let mut len = index_size / 8;
+        let mut offset = 0;
+        while len > 0 {
+            index.seek(SeekFrom::Start((len - 1) * 8))?;
+            offset = index.read_u64()?;
+            if offset <= data_size {
+                break
+            }
+            if !recover {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Index refers past end of data file"));
+            }
+            len -= 1;
+            offset = 0;
+        }
+        if len < index_size / 8 {
+            index.set_len(len * 8)?;
+        }
+        index.seek(SeekFrom::End(0))?;
+        if data_size > offset {
+            if !recover {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Data file extends past last indexed record"));
+            }
+            data.set_len(offset)?;
+        }
+        data.seek(SeekFrom::End(0))?;
+        Ok(Self { data: BufWriter::new(data), index: BufWriter::new(index), offset, len })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn append(&mut self, record: &[u8]) -> io::Result<u64> {
+        self.data.write_all(record)?;
+        self.offset += record.len() as u64;
+        self.index.write_u64(self.offset)?;
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+
+    pub fn append_with(&mut self, write_record: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> io::Result<u64> {
+        let mut record = Vec::new();
+        write_record(&mut record)?;
+        self.append(&record)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.data.flush()?;
+        self.index.flush()
+    }
+}
+
+pub struct IndexedRecordReader {
+    data: MappedFile,
+    index: MappedFile,
+}
+
+impl IndexedRecordReader {
+    pub fn open(data_path: impl AsRef<Path>, index_path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = MappedFile::open(data_path)?;
+        let index = MappedFile::open(index_path)?;
+        if !index.len().is_multiple_of(8) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Index size is not a multiple of 8"));
+        }
+        let reader = Self { data, index };
+        let last = reader.len().checked_sub(1).and_then(|i| reader.end_of(i));
+        if last.is_some_and(|end| end > reader.data.len()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Index refers past end of data file"));
+        }
+        Ok(reader)
+    }
+
+    fn end_of(&self, index: usize) -> Option<usize> {
+        let start = index.checked_mul(8)?;
+        self.index.get(start..start.checked_add(8)?).map(|x| read_be_u64(x) as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len() / 8
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn record(&self, index: usize) -> Option<&[u8]> {
+        let end = self.end_of(index)?;
+        let start = if index == 0 { 0 } else { self.end_of(index - 1)? };
+        self.data.get(start..end)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> {
+        (0..self.len()).filter_map(|i| self.record(i))
+    }
+}
+
+#[cfg(test)]
+mod record_file_tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use crate::read_be_u32;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ferrite-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn test_fixed_records() {
+        let path = temp_path("fixed.bin");
+        let mut bytes = Vec::new();
+        for i in 0..100u32 {
+            bytes.write_u32(i).unwrap();
+            bytes.write_u64(i as u64 * 3).unwrap();
+        }
+        fs::write(&path, &bytes).unwrap();
+
+        let reader = FixedRecordReader::open(&path, 12).unwrap();
+        assert_eq!(reader.len(), 100);
+        let record = reader.record(42).unwrap();
+        assert_eq!(read_be_u32(&record[..4]), 42);
+        assert_eq!(read_be_u64(&record[4..]), 126);
+        assert!(reader.record(100).is_none());
+        assert!(reader.record(usize::MAX / 12).is_none());
+        assert!(reader.record(usize::MAX).is_none());
+        assert_eq!(reader.iter().count(), 100);
+        assert!(FixedRecordReader::open(&path, 7).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_indexed_records() {
+        let data_path = temp_path("indexed.bin");
+        let index_path = temp_path("indexed.idx");
+        let _ = fs::remove_file(&data_path);
+        let _ = fs::remove_file(&index_path);
+
+        let mut writer = IndexedRecordWriter::open(&data_path, &index_path).unwrap();
+        assert_eq!(writer.append(b"first").unwrap(), 0);
+        assert_eq!(writer.append(b"").unwrap(), 1);
+        writer.flush().unwrap();
+        drop(writer);
+
+        let mut writer = IndexedRecordWriter::open(&data_path, &index_path).unwrap();
+        assert_eq!(writer.len(), 2);
+        assert_eq!(writer.append_with(|w| w.write_string_len16("third")).unwrap(), 2);
+        writer.flush().unwrap();
+
+        let reader = IndexedRecordReader::open(&data_path, &index_path).unwrap();
+        assert_eq!(reader.len(), 3);
+        assert_eq!(reader.record(0).unwrap(), b"first");
+        assert_eq!(reader.record(1).unwrap(), b"");
+        assert_eq!(reader.record(2).unwrap().read_string_len16().unwrap(), "third");
+        assert!(reader.record(3).is_none());
+        assert!(reader.record(usize::MAX / 8).is_none());
+        fs::remove_file(&data_path).unwrap();
+        fs::remove_file(&index_path).unwrap();
+    }
+
+    #[test]
+    fn test_unindexed_data() {
+        let data_path = temp_path("unindexed.bin");
+        let index_path = temp_path("unindexed.idx");
+        let _ = fs::remove_file(&index_path);
+        fs::write(&data_path, b"twenty-two bytes of it").unwrap();
+
+        let err = IndexedRecordWriter::open(&data_path, &index_path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::metadata(&data_path).unwrap().len(), 22);
+
+        fs::write(&index_path, [0, 0, 0]).unwrap();
+        let err = IndexedRecordWriter::open(&data_path, &index_path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::metadata(&index_path).unwrap().len(), 3);
+
+        let writer = IndexedRecordWriter::recover(&data_path, &index_path).unwrap();
+        assert!(writer.is_empty());
+        assert_eq!(fs::metadata(&data_path).unwrap().len(), 0);
+        assert_eq!(fs::metadata(&index_path).unwrap().len(), 0);
+        fs::remove_file(&data_path).unwrap();
+        fs::remove_file(&index_path).unwrap();
+    }
+
+    #[test]
+    fn test_index_ahead_of_data() {
+        let data_path = temp_path("ahead.bin");
+        let index_path = temp_path("ahead.idx");
+        let _ = fs::remove_file(&data_path);
+        let _ = fs::remove_file(&index_path);
+
+        let mut writer = IndexedRecordWriter::open(&data_path, &index_path).unwrap();
+        writer.append(b"first").unwrap();
+        writer.append(b"second").unwrap();
+        writer.flush().unwrap();
+        drop(writer);
+        OpenOptions::new().write(true).open(&data_path).unwrap().set_len(5).unwrap();
+
+        let err = IndexedRecordWriter::open(&data_path, &index_path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::metadata(&index_path).unwrap().len(), 16);
+
+        let mut writer = IndexedRecordWriter::recover(&data_path, &index_path).unwrap();
+        assert_eq!(writer.len(), 1);
+        assert_eq!(fs::metadata(&index_path).unwrap().len(), 8);
+        assert_eq!(writer.append(b"third").unwrap(), 1);
+        writer.flush().unwrap();
+
+        let reader = IndexedRecordReader::open(&data_path, &index_path).unwrap();
+        assert_eq!(reader.len(), 2);
+        assert_eq!(reader.record(0).unwrap(), b"first");
+        assert_eq!(reader.record(1).unwrap(), b"third");
+        fs::remove_file(&data_path).unwrap();
+        fs::remove_file(&index_path).unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt::{Display, Formatter, Write as _};
+use std::io;
+use crate::ReadExt;
+
+pub fn hexdump(bytes: &[u8]) -> String {
+    hexdump_at(bytes, 0)
+}
+
+pub fn hexdump_at(bytes: &[u8], base_offset: u64) -> String {
+    let mut out = String::new();
+    for (i, line) in bytes.chunks(16).enumerate() {
+        write!(out, "{:08x} ", base_offset + i as u64 * 16).unwrap();
+        for j in 0..16 {
+            if j == 8 {
+                out.push(' ');
+            }
+            match line.get(j) {
+                Some(byte) => write!(out, " {byte:02x}").unwrap(),
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str("  |");
+        out.extend(line.iter().map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' }));
+        out.push_str("|\n");
+    }
+    out
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceEntry {
+    pub offset: u64,
+    pub len: u64,
+    pub kind: &'static str,
+    pub outcome: Result<String, String>,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.outcome {
+            Ok(value) => write!(f, "{:08x}  {:<12} {value}", self.offset, self.kind),
+            Err(e) => write!(f, "{:08x}  {:<12} FAILED: {e}", self.offset, self.kind),
+        }
+    }
+}
+
+pub struct TraceReader<R> {
+    inner: R,
+    offset: u64,
+    trace: Vec<TraceEntry>,
+    typed: bool,
+}
+
+impl<R: io::Read> TraceReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, offset: 0, trace: Vec::new(), typed: false }
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    pub fn report(&self) -> String {
+        self.trace.iter().map(|entry| format!("{entry}\n")).collect()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn traced<T: std::fmt::Debug>(
+        &mut self,
+        kind: &'static str,
+        read: impl FnOnce(&mut Self) -> io::Result<T>,
+    ) -> io::Result<T> {
+        let offset = self.offset;
+        let typed = std::mem::replace(&mut self.typed, true);
+        let result = read(self);
+        self.typed = typed;
+        if typed {
+            return result
+        }
+        let outcome = match &result {
+            Ok(value) => Ok(format!("{value:?}")),
+            Err(e) => Err(e.to_string()),
+        };
+        self.trace.push(TraceEntry { offset, len: self.offset - offset, kind, outcome });
+        result
+    }
+
+    pub fn read_u8(&mut self) -> io::Result<u8> {
+        self.traced("u8", ReadExt::read_u8)
+    }
+
+    pub fn read_u16(&mut self) -> io::Result<u16> {
+        self.traced("u16", ReadExt::read_u16)
+    }
+
+    pub fn read_u32(&mut self) -> io::Result<u32> {
+        self.traced("u32", ReadExt::read_u32)
+    }
+
+    pub fn read_u64(&mut self) -> io::Result<u64> {
+        self.traced("u64", ReadExt::read_u64)
+    }
+
+    pub fn read_i64(&mut self) -> io::Result<i64> {
+        self.traced("i64", ReadExt::read_i64)
+    }
+
+    pub fn read_f64(&mut self) -> io::Result<f64> {
+        self.traced("f64", ReadExt::read_f64)
+    }
+
+    pub fn read_string_len16(&mut self) -> io::Result<String> {
+        self.traced("string_len16", ReadExt::read_string_len16)
+    }
+
+    pub fn read_string_len32(&mut self) -> io::Result<String> {
+        self.traced("string_len32", ReadExt::read_string_len32)
+    }
+}
+
+impl<R: io::Read> io::Read for TraceReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let offset = self.offset;
+        let result = self.inner.read(buf);
+        if let Ok(n) = result {
+            self.offset += n as u64;
+        }
+        if !self.typed && !buf.is_empty() {
+            let outcome = match &result {
+                Ok(n) => Ok(hex_preview(&buf[..*n])),
+                Err(e) => Err(e.to_string()),
+            };
+            self.trace.push(TraceEntry { offset, len: self.offset - offset, kind: "bytes", outcome });
+        }
+        result
+    }
+}
+
+fn hex_preview(bytes: &[u8]) -> String {
+    let mut out = format!("[{}]", bytes.len());
+    for byte in bytes.iter().take(16) {
+        write!(out, " {byte:02x}").unwrap();
+    }
+    if bytes.len() > 16 {
+        out.push_str(" ..");
+    }
+    out
+}
+
+#[cfg(test)]
+mod trace_io_tests {
+    use super::*;
+    use crate::{BinaryCodec, WriteExt};
+
+    #[test]
+    fn test_hexdump() {
+        let dump = hexdump(b"Hello, world!\n\x00\x01\xffABC");
+        assert_eq!(dump, "\
+00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+00000010  ff 41 42 43                                       |.ABC|
+");
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut bytes = Vec::new();
+        bytes.write_u16(7).unwrap();
+        bytes.write_string_len16("abc").unwrap();
+        bytes.write_u8(1).unwrap();
+
+        let mut reader = TraceReader::new(bytes.as_slice());
+        assert_eq!(reader.read_u16().unwrap(), 7);
+        assert_eq!(reader.read_string_len16().unwrap(), "abc");
+        assert!(reader.read_u32().is_err());
+        assert_eq!(reader.trace()[1], TraceEntry { offset: 2, len: 5, kind: "string_len16", outcome: Ok("\"abc\"".into()) });
+        assert_eq!(reader.trace().len(), 3);
+        assert_eq!(reader.trace()[2].offset, 7);
+        assert!(reader.trace()[2].outcome.is_err());
+        assert!(reader.report().lines().last().unwrap().starts_with("00000007  u32          FAILED"));
+    }
+
+    #[test]
+    fn test_trace_generic_decoder() {
+        let mut bytes = Vec::new();
+        (7u32, "abc".to_string()).encode(&mut bytes).unwrap();
+
+        let mut reader = TraceReader::new(bytes.as_slice());
+        let value = <(u32, String)>::decode(&mut reader).unwrap();
+        assert_eq!(value, (7, "abc".to_string()));
+        let trace = reader.trace();
+        assert!(!trace.is_empty());
+        assert!(trace.iter().all(|entry| entry.kind == "bytes"));
+        assert_eq!(trace[0], TraceEntry { offset: 0, len: 4, kind: "bytes", outcome: Ok("[4] 00 00 00 07".into()) });
+        assert_eq!(trace.iter().map(|entry| entry.len).sum::<u64>(), bytes.len() as u64);
+        assert!(trace.windows(2).all(|w| w[0].offset + w[0].len == w[1].offset));
+
+        let mut reader = TraceReader::new(&bytes[..6]);
+        assert!(<(u32, String)>::decode(&mut reader).is_err());
+        assert_eq!(reader.trace().last().unwrap().len, 0);
+    }
+}